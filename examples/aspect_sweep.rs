@@ -0,0 +1,172 @@
+//! Renders the HUD at a list of aspect ratios to PNG files, without opening a window.
+//!
+//! ```sh
+//! cargo run --example aspect_sweep --features testing -- [OUT_DIR] [ASPECT...]
+//! cargo run --example aspect_sweep --features testing -- sweep 4:3 16:9 21:9 9:16
+//! ```
+//!
+//! Each aspect ratio is rendered at a height of 720 pixels into `OUT_DIR/<width>x<height>.png`
+//! (`aspect_sweep` by default). Replace `spawn_scene` with your game's HUD setup to review every
+//! layout at once, e.g. in a pull request.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use bevy::{
+    app::ScheduleRunnerPlugin,
+    camera::RenderTarget,
+    color::palettes::css::{ORANGE, TEAL},
+    prelude::*,
+    render::{
+        render_resource::TextureFormat,
+        view::screenshot::{save_to_disk, Screenshot, ScreenshotCaptured},
+    },
+    window::ExitCondition,
+    winit::WinitPlugin,
+};
+use bevy_aspect_ratio_mask::{
+    AspectRatioCamera, AspectRatioPlugin, Hud, HudSet, Resolution, SyntheticWindowSize,
+};
+
+const RESOLUTION_WIDTH: f32 = 960.0;
+const RESOLUTION_HEIGHT: f32 = 540.0;
+/// The height of every rendered image, in pixels.
+const HEIGHT: u32 = 720;
+/// Frames to wait after switching aspect ratio, so layout, fonts and rendering catch up.
+const SETTLE_FRAMES: u32 = 5;
+const DEFAULT_ASPECTS: [&str; 6] = ["4:3", "16:10", "16:9", "21:9", "32:9", "9:16"];
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let out_dir = PathBuf::from(args.next().unwrap_or_else(|| "aspect_sweep".into()));
+    let mut aspects: Vec<f32> = args.map(|arg| parse_aspect(&arg)).collect();
+    if aspects.is_empty() {
+        aspects = DEFAULT_ASPECTS
+            .iter()
+            .map(|arg| parse_aspect(arg))
+            .collect();
+    }
+    std::fs::create_dir_all(&out_dir).expect("could not create the output directory");
+
+    App::new()
+        .add_plugins(
+            DefaultPlugins
+                .set(WindowPlugin {
+                    primary_window: None,
+                    exit_condition: ExitCondition::DontExit,
+                    ..default()
+                })
+                // No display server is needed: the HUD is rendered into images.
+                .disable::<WinitPlugin>(),
+        )
+        .add_plugins(ScheduleRunnerPlugin::run_loop(Duration::from_secs_f64(
+            1.0 / 60.0,
+        )))
+        .add_plugins(AspectRatioPlugin {
+            resolution: Resolution {
+                width: RESOLUTION_WIDTH,
+                height: RESOLUTION_HEIGHT,
+            },
+            ..default()
+        })
+        .insert_resource(Sweep {
+            out_dir,
+            aspects,
+            current: 0,
+            frames: 0,
+            target: Handle::default(),
+            captured: false,
+        })
+        .add_systems(Startup, spawn_scene)
+        .add_systems(Update, sweep)
+        .run();
+}
+
+/// Parses `W:H` or a plain ratio such as `1.78`.
+fn parse_aspect(arg: &str) -> f32 {
+    let aspect = match arg.split_once(':') {
+        Some((width, height)) => width.parse::<f32>().ok().zip(height.parse::<f32>().ok()),
+        None => arg.parse::<f32>().ok().map(|aspect| (aspect, 1.0)),
+    };
+    match aspect {
+        Some((width, height)) if width > 0.0 && height > 0.0 => width / height,
+        _ => panic!("invalid aspect ratio {arg:?}, expected e.g. 16:9 or 1.78"),
+    }
+}
+
+#[derive(Resource)]
+struct Sweep {
+    out_dir: PathBuf,
+    aspects: Vec<f32>,
+    /// The aspect ratio being rendered.
+    current: usize,
+    /// Frames since the current aspect ratio was applied.
+    frames: u32,
+    /// The image the current aspect ratio is rendered into.
+    target: Handle<Image>,
+    /// Whether the current aspect ratio has been saved.
+    captured: bool,
+}
+
+#[derive(Component)]
+struct SweepCamera;
+
+/// An example scene: a world sprite at the center and HUD text in the corners.
+fn spawn_scene(mut commands: Commands, hud: Res<Hud>) {
+    commands.spawn((Camera2d, AspectRatioCamera, IsDefaultUiCamera, SweepCamera));
+    commands.spawn((
+        Sprite::from_color(TEAL, Vec2::new(RESOLUTION_WIDTH, RESOLUTION_HEIGHT)),
+        Transform::from_xyz(0.0, 0.0, -1.0),
+    ));
+    commands.spawn(Sprite::from_color(ORANGE, Vec2::splat(40.0)));
+    HudSet::builder()
+        .margin(16.0)
+        .top_left(Text::new("Score: 0"))
+        .top_right(Text::new("Lives: 3"))
+        .bottom(Text::new("Press Space"))
+        .spawn(&mut commands, hud.0);
+}
+
+/// Renders each aspect ratio in turn and exits after the last one.
+fn sweep(
+    mut commands: Commands,
+    mut sweep: ResMut<Sweep>,
+    mut images: ResMut<Assets<Image>>,
+    camera: Single<Entity, With<SweepCamera>>,
+    mut exit: MessageWriter<AppExit>,
+) {
+    if sweep.captured {
+        sweep.current += 1;
+        sweep.frames = 0;
+        sweep.captured = false;
+    }
+    let Some(&aspect) = sweep.aspects.get(sweep.current) else {
+        exit.write(AppExit::Success);
+        return;
+    };
+    let width = (HEIGHT as f32 * aspect).round() as u32;
+
+    if sweep.frames == 0 {
+        sweep.target = images.add(Image::new_target_texture(
+            width,
+            HEIGHT,
+            TextureFormat::bevy_default(),
+            None,
+        ));
+        commands
+            .entity(*camera)
+            .insert(RenderTarget::Image(sweep.target.clone().into()));
+        commands.insert_resource(SyntheticWindowSize::new(width as f32, HEIGHT as f32));
+    }
+    sweep.frames += 1;
+
+    if sweep.frames == SETTLE_FRAMES {
+        let path = sweep.out_dir.join(format!("{width}x{HEIGHT}.png"));
+        commands
+            .spawn(Screenshot::image(sweep.target.clone()))
+            .observe(save_to_disk(path))
+            .observe(|_: On<ScreenshotCaptured>, mut sweep: ResMut<Sweep>| {
+                sweep.captured = true;
+            });
+    }
+}
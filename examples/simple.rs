@@ -36,7 +36,7 @@ fn main() {
 
 fn setup(mut commands: Commands, hud: Res<Hud>) {
     commands.spawn((
-        Camera2d::default(),
+        Camera2d,
         Projection::from(OrthographicProjection {
             scaling_mode: ScalingMode::AutoMin {
                 min_width: RESOLUTION_WIDTH,
@@ -0,0 +1,86 @@
+use accesskit::{Node, Rect as AccessKitRect, Role};
+use bevy::a11y::AccessibilityNode;
+use bevy::prelude::*;
+use bevy::ui::{ComputedNode, UiGlobalTransform};
+
+use crate::Hud;
+
+/// An accessible name for a UI element, read by screen readers.
+///
+/// Give one to HUD layers, panels or anchored elements that have no text of their own. The
+/// element is exposed as a group with this label if it isn't already exposed as something
+/// else (such as a button).
+#[derive(Component, Clone, Debug, PartialEq, Eq)]
+pub struct AccessibleLabel(pub String);
+
+/// The accessibility node of a region of the virtual layout, like the HUD or a nested viewport.
+pub(crate) fn region_node() -> AccessibilityNode {
+    AccessibilityNode::from(Node::new(Role::Group))
+}
+
+/// The accessibility node of the mask, hiding the bars and their decorations.
+pub(crate) fn hidden_node() -> AccessibilityNode {
+    let mut node = Node::new(Role::GenericContainer);
+    node.set_hidden();
+    AccessibilityNode::from(node)
+}
+
+pub(crate) fn apply_accessible_labels(
+    mut commands: Commands,
+    mut labels: Query<
+        (Entity, &AccessibleLabel, Option<&mut AccessibilityNode>),
+        Changed<AccessibleLabel>,
+    >,
+) {
+    for (entity, label, accessible) in labels.iter_mut() {
+        match accessible {
+            Some(mut accessible) => accessible.set_label(label.0.as_str()),
+            None => {
+                let mut node = Node::new(Role::Group);
+                node.set_label(label.0.as_str());
+                commands
+                    .entity(entity)
+                    .try_insert(AccessibilityNode::from(node));
+            }
+        }
+    }
+}
+
+/// Sets the screen-space bounds of the HUD's accessible nodes from their full
+/// `UiGlobalTransform`.
+///
+/// `bevy_ui` only uses the translation, which misplaces anything scaled with a `UiTransform`,
+/// such as the HUD in embedded mode or a `NestedViewport`. Runs in `PostUpdate` once
+/// transforms are propagated and before `AccessibilitySystems::Update` pushes the tree to
+/// AccessKit, so assistive technology gets the letterboxed geometry in the same frame.
+/// `bevy_ui` writes its own bounds in an unnamed system, which can't be ordered against; bounds
+/// it overwrites after a relayout are corrected again on the next frame.
+pub(crate) fn update_accessible_bounds(
+    hud: Res<Hud>,
+    children: Query<&Children>,
+    mut nodes: Query<(&mut AccessibilityNode, &ComputedNode, &UiGlobalTransform)>,
+) {
+    for entity in std::iter::once(hud.0).chain(children.iter_descendants(hud.0)) {
+        let Ok((mut accessible, node, transform)) = nodes.get_mut(entity) else {
+            continue;
+        };
+        let half_size = node.size() / 2.0;
+        let corners = [
+            Vec2::new(-half_size.x, -half_size.y),
+            Vec2::new(half_size.x, -half_size.y),
+            Vec2::new(-half_size.x, half_size.y),
+            half_size,
+        ]
+        .map(|corner| transform.transform_point2(corner));
+        let min = corners
+            .iter()
+            .fold(Vec2::MAX, |min, corner| min.min(*corner));
+        let max = corners
+            .iter()
+            .fold(Vec2::MIN, |max, corner| max.max(*corner));
+        let bounds = AccessKitRect::new(min.x as f64, min.y as f64, max.x as f64, max.y as f64);
+        if accessible.bounds() != Some(bounds) {
+            accessible.set_bounds(bounds);
+        }
+    }
+}
@@ -0,0 +1,248 @@
+use bevy::prelude::*;
+
+/// One of the nine places a HUD element can be anchored to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HudAnchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Center,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl HudAnchor {
+    /// All nine anchors, row by row from the top-left.
+    pub const ALL: [HudAnchor; 9] = [
+        HudAnchor::TopLeft,
+        HudAnchor::Top,
+        HudAnchor::TopRight,
+        HudAnchor::Left,
+        HudAnchor::Center,
+        HudAnchor::Right,
+        HudAnchor::BottomLeft,
+        HudAnchor::Bottom,
+        HudAnchor::BottomRight,
+    ];
+
+    /// The anchor's horizontal and vertical alignment.
+    fn alignment(self) -> (JustifyContent, AlignItems) {
+        let vertical = match self {
+            HudAnchor::TopLeft | HudAnchor::Top | HudAnchor::TopRight => JustifyContent::FlexStart,
+            HudAnchor::Left | HudAnchor::Center | HudAnchor::Right => JustifyContent::Center,
+            _ => JustifyContent::FlexEnd,
+        };
+        let horizontal = match self {
+            HudAnchor::TopLeft | HudAnchor::Left | HudAnchor::BottomLeft => AlignItems::FlexStart,
+            HudAnchor::Top | HudAnchor::Center | HudAnchor::Bottom => AlignItems::Center,
+            _ => AlignItems::FlexEnd,
+        };
+        (vertical, horizontal)
+    }
+
+    /// A node covering its parent that places its children at this anchor, `margin` virtual
+    /// pixels from the edges. Children are stacked top to bottom.
+    pub fn slot_node(self, margin: f32) -> Node {
+        let (justify_content, align_items) = self.alignment();
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            padding: UiRect::all(Val::Px(margin)),
+            flex_direction: FlexDirection::Column,
+            justify_content,
+            align_items,
+            ..default()
+        }
+    }
+}
+
+type SpawnElement = Box<dyn FnOnce(&mut EntityCommands)>;
+
+/// Declares a set of HUD elements anchored to the corners, edges and center of the HUD, all
+/// with the same margin, to spawn in one call.
+///
+/// Each anchor used gets a full-size slot node that ignores picking and aligns its children;
+/// elements sharing an anchor are stacked top to bottom in the order they were added.
+///
+/// ```rust,no_run
+/// # use bevy::prelude::*;
+/// # use bevy_aspect_ratio_mask::{Hud, HudAnchor, HudSet};
+/// fn setup(mut commands: Commands, hud: Res<Hud>) {
+///     let set = HudSet::builder()
+///         .margin(16.0)
+///         .top_left(Text::new("Score: 0"))
+///         .top_right(Text::new("Lives: 3"))
+///         .bottom(Text::new("Press Space"))
+///         .spawn(&mut commands, hud.0);
+///     let score = set.element(HudAnchor::TopLeft);
+/// }
+/// ```
+#[derive(Default)]
+pub struct HudSetBuilder {
+    margin: f32,
+    elements: Vec<(HudAnchor, SpawnElement)>,
+}
+
+impl HudSetBuilder {
+    /// Keeps every element `margin` virtual pixels from the HUD's edges (zero by default).
+    pub fn margin(mut self, margin: f32) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// Adds an element at `anchor`.
+    pub fn at(mut self, anchor: HudAnchor, bundle: impl Bundle) -> Self {
+        self.elements.push((
+            anchor,
+            Box::new(move |element: &mut EntityCommands| {
+                element.insert(bundle);
+            }),
+        ));
+        self
+    }
+
+    /// Adds an element in the top-left corner.
+    pub fn top_left(self, bundle: impl Bundle) -> Self {
+        self.at(HudAnchor::TopLeft, bundle)
+    }
+
+    /// Adds an element centered on the top edge.
+    pub fn top(self, bundle: impl Bundle) -> Self {
+        self.at(HudAnchor::Top, bundle)
+    }
+
+    /// Adds an element in the top-right corner.
+    pub fn top_right(self, bundle: impl Bundle) -> Self {
+        self.at(HudAnchor::TopRight, bundle)
+    }
+
+    /// Adds an element centered on the left edge.
+    pub fn left(self, bundle: impl Bundle) -> Self {
+        self.at(HudAnchor::Left, bundle)
+    }
+
+    /// Adds an element in the center.
+    pub fn center(self, bundle: impl Bundle) -> Self {
+        self.at(HudAnchor::Center, bundle)
+    }
+
+    /// Adds an element centered on the right edge.
+    pub fn right(self, bundle: impl Bundle) -> Self {
+        self.at(HudAnchor::Right, bundle)
+    }
+
+    /// Adds an element in the bottom-left corner.
+    pub fn bottom_left(self, bundle: impl Bundle) -> Self {
+        self.at(HudAnchor::BottomLeft, bundle)
+    }
+
+    /// Adds an element centered on the bottom edge.
+    pub fn bottom(self, bundle: impl Bundle) -> Self {
+        self.at(HudAnchor::Bottom, bundle)
+    }
+
+    /// Adds an element in the bottom-right corner.
+    pub fn bottom_right(self, bundle: impl Bundle) -> Self {
+        self.at(HudAnchor::BottomRight, bundle)
+    }
+
+    /// Spawns the set under `parent`, usually the `Hud`.
+    pub fn spawn(self, commands: &mut Commands, parent: Entity) -> HudSet {
+        let root = commands
+            .spawn((
+                Name::new("Hud Set"),
+                Node {
+                    position_type: PositionType::Absolute,
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                Pickable::IGNORE,
+                ChildOf(parent),
+            ))
+            .id();
+
+        let mut set = HudSet {
+            root,
+            slots: Vec::new(),
+            elements: Vec::new(),
+        };
+        for (anchor, spawn_element) in self.elements {
+            let slot = match set.slot(anchor) {
+                Some(slot) => slot,
+                None => {
+                    let slot = commands
+                        .spawn((
+                            Name::new("Hud Set Slot"),
+                            anchor.slot_node(self.margin),
+                            Pickable::IGNORE,
+                            ChildOf(root),
+                        ))
+                        .id();
+                    set.slots.push((anchor, slot));
+                    slot
+                }
+            };
+            let mut element = commands.spawn(ChildOf(slot));
+            spawn_element(&mut element);
+            set.elements.push((anchor, element.id()));
+        }
+        set
+    }
+}
+
+/// The entities of a spawned `HudSetBuilder`, for later updates.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HudSet {
+    /// The full-size node holding the set.
+    pub root: Entity,
+    slots: Vec<(HudAnchor, Entity)>,
+    elements: Vec<(HudAnchor, Entity)>,
+}
+
+impl HudSet {
+    /// Starts declaring a set.
+    pub fn builder() -> HudSetBuilder {
+        HudSetBuilder::default()
+    }
+
+    /// The first element added at `anchor`.
+    pub fn element(&self, anchor: HudAnchor) -> Option<Entity> {
+        self.elements_at(anchor).next()
+    }
+
+    /// The elements at `anchor`, in the order they were added.
+    pub fn elements_at(&self, anchor: HudAnchor) -> impl Iterator<Item = Entity> + '_ {
+        self.elements
+            .iter()
+            .filter(move |(element_anchor, _)| *element_anchor == anchor)
+            .map(|(_, element)| *element)
+    }
+
+    /// All elements with their anchors, in the order they were added.
+    pub fn elements(&self) -> &[(HudAnchor, Entity)] {
+        &self.elements
+    }
+
+    /// The slot node aligning the elements at `anchor`, if any were added there.
+    ///
+    /// Spawn more children into it to add elements later.
+    pub fn slot(&self, anchor: HudAnchor) -> Option<Entity> {
+        self.slots
+            .iter()
+            .find(|(slot_anchor, _)| *slot_anchor == anchor)
+            .map(|(_, slot)| *slot)
+    }
+
+    /// Changes the margin of every element of the set.
+    pub fn set_margin(&self, commands: &mut Commands, margin: f32) {
+        for (anchor, slot) in &self.slots {
+            commands.entity(*slot).insert(anchor.slot_node(margin));
+        }
+    }
+}
@@ -0,0 +1,266 @@
+use bevy::prelude::*;
+use bevy::ui::{ComputedNode, UiGlobalTransform};
+
+use crate::layout::LayoutSource;
+use crate::{Hud, Letterbox, Resolution, ViewportCoords};
+
+/// Scales a HUD layer from `from` to `to` around a pivot in virtual pixels, e.g. to pop a
+/// menu open.
+///
+/// A `UiTransform` scales around the node's own center, and under letterboxing the point
+/// that should stay put is easy to get wrong. This animation keeps `pivot` fixed instead,
+/// given in the HUD's virtual pixels and defaulting to the center of the virtual area,
+/// by translating the node as it scales. It drives the node's `UiTransform`, so the node
+/// must not be transformed otherwise while it plays. The final scale is kept once done.
+///
+/// ```rust,no_run
+/// # use bevy::prelude::*;
+/// # use bevy_aspect_ratio_mask::{Hud, HudScaleAnimation};
+/// fn open_menu(mut commands: Commands, hud: Res<Hud>) {
+///     commands.entity(hud.0).with_child((
+///         Node {
+///             width: Val::Percent(100.0),
+///             height: Val::Percent(100.0),
+///             ..default()
+///         },
+///         HudScaleAnimation::pop(),
+///     ));
+/// }
+/// ```
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+#[require(UiTransform)]
+pub struct HudScaleAnimation {
+    /// The scale at the start of the animation.
+    pub from: f32,
+    /// The scale at the end of the animation.
+    pub to: f32,
+    /// How long the animation takes, in seconds.
+    pub duration: f32,
+    /// The easing applied to the animation's progress.
+    pub ease: EaseFunction,
+    /// The point kept in place, in the HUD's virtual pixels. `None` uses the center of the
+    /// virtual area.
+    pub pivot: Option<Vec2>,
+    elapsed: f32,
+    translation: Vec2,
+}
+
+impl HudScaleAnimation {
+    /// Scales from `from` to `to` over `duration` seconds with a smooth ease.
+    pub fn new(from: f32, to: f32, duration: f32) -> Self {
+        Self {
+            from,
+            to,
+            duration,
+            ease: EaseFunction::CubicOut,
+            pivot: None,
+            elapsed: 0.0,
+            translation: Vec2::ZERO,
+        }
+    }
+
+    /// A short pop-in that overshoots slightly, suited to opening menus.
+    pub fn pop() -> Self {
+        Self {
+            ease: EaseFunction::BackOut,
+            ..Self::new(0.8, 1.0, 0.25)
+        }
+    }
+
+    /// Keeps `pivot`, in the HUD's virtual pixels, in place instead of the virtual-area center.
+    pub fn with_pivot(mut self, pivot: Vec2) -> Self {
+        self.pivot = Some(pivot);
+        self
+    }
+
+    /// Uses `ease` for the animation's progress.
+    pub fn with_ease(mut self, ease: EaseFunction) -> Self {
+        self.ease = ease;
+        self
+    }
+
+    /// Whether the animation has reached its final scale.
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+
+    /// The scale at the current point of the animation.
+    pub fn scale(&self) -> f32 {
+        let progress = if self.duration > 0.0 {
+            (self.elapsed / self.duration).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        self.from + (self.to - self.from) * self.ease.sample_clamped(progress)
+    }
+}
+
+pub(crate) fn animate_hud_scale(
+    time: Res<Time>,
+    hud: Res<Hud>,
+    letterbox: Res<Letterbox>,
+    coords: ViewportCoords,
+    mut animations: Query<(
+        &mut HudScaleAnimation,
+        &mut UiTransform,
+        &ComputedNode,
+        &UiGlobalTransform,
+    )>,
+) {
+    let center = letterbox.virtual_size() / 2.0;
+    for (mut animation, mut transform, node, global) in animations.iter_mut() {
+        if node.size().min_element() <= 0.0 {
+            // Not laid out yet: hold the first frame until the node's center is known.
+            transform.scale = Vec2::splat(animation.from);
+            continue;
+        }
+        if animation.is_finished() && transform.scale == Vec2::splat(animation.to) {
+            continue;
+        }
+        let Some(node_center) = coords.physical_to_viewport(hud.0, global.translation) else {
+            continue;
+        };
+
+        animation.elapsed += time.delta_secs();
+        let scale = animation.scale();
+
+        // The laid-out center, without the translation applied on the previous frame.
+        let node_center = node_center - animation.translation;
+        let pivot = animation.pivot.unwrap_or(center);
+        let translation = (pivot - node_center) * (1.0 - scale);
+        animation.translation = translation;
+
+        transform.scale = Vec2::splat(scale);
+        transform.translation = Val2::px(translation.x, translation.y);
+    }
+}
+
+/// Moves an entity by an offset given in virtual pixels, so it travels the same visual
+/// distance at every window size.
+///
+/// The offset goes from `from` to `to` (x right, y down) and is converted through the current
+/// layout every frame, so resizing mid-animation keeps it on track:
+///
+/// - UI nodes are offset through their `UiTransform` translation, converted from virtual
+///   pixels to the node's own UI pixels. This works the same inside the HUD, in the bars and
+///   outside the HUD in embedded mode, where one UI pixel is not one virtual pixel.
+/// - Other entities are moved through their `Transform`, converted to world units as the
+///   camera shows them under the active `FitPolicy`. The offset is applied on top of the
+///   entity's own movement.
+///
+/// The final offset is kept once done.
+///
+/// ```rust,no_run
+/// # use bevy::prelude::*;
+/// # use bevy_aspect_ratio_mask::{Hud, VirtualTween};
+/// fn slide_in_banner(mut commands: Commands, hud: Res<Hud>) {
+///     commands.entity(hud.0).with_child((
+///         Node::default(),
+///         Text::new("Stage clear!"),
+///         VirtualTween::new(Vec2::new(-200.0, 0.0), Vec2::ZERO, 0.4),
+///     ));
+/// }
+/// ```
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct VirtualTween {
+    /// The offset at the start of the tween, in virtual pixels.
+    pub from: Vec2,
+    /// The offset at the end of the tween, in virtual pixels.
+    pub to: Vec2,
+    /// How long the tween takes, in seconds.
+    pub duration: f32,
+    /// The easing applied to the tween's progress.
+    pub ease: EaseFunction,
+    elapsed: f32,
+    /// The `Transform` offset applied so far, in world units.
+    applied: Vec2,
+}
+
+impl VirtualTween {
+    /// Offsets from `from` to `to` virtual pixels over `duration` seconds with a smooth ease.
+    pub fn new(from: Vec2, to: Vec2, duration: f32) -> Self {
+        Self {
+            from,
+            to,
+            duration,
+            ease: EaseFunction::CubicOut,
+            elapsed: 0.0,
+            applied: Vec2::ZERO,
+        }
+    }
+
+    /// Slides by `distance` virtual pixels from where the entity is.
+    pub fn slide(distance: Vec2, duration: f32) -> Self {
+        Self::new(Vec2::ZERO, distance, duration)
+    }
+
+    /// Uses `ease` for the tween's progress.
+    pub fn with_ease(mut self, ease: EaseFunction) -> Self {
+        self.ease = ease;
+        self
+    }
+
+    /// Whether the tween has reached its final offset.
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+
+    /// The offset at the current point of the tween, in virtual pixels.
+    pub fn offset(&self) -> Vec2 {
+        let progress = if self.duration > 0.0 {
+            (self.elapsed / self.duration).clamp(0.0, 1.0)
+        } else {
+            1.0
+        };
+        self.from.lerp(self.to, self.ease.sample_clamped(progress))
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub(crate) fn animate_virtual_tweens(
+    time: Res<Time>,
+    letterbox: Res<Letterbox>,
+    resolution: Res<Resolution>,
+    source: LayoutSource,
+    parents: Query<&ChildOf>,
+    globals: Query<&UiGlobalTransform>,
+    mut tweens: Query<(
+        Entity,
+        &mut VirtualTween,
+        Option<&ComputedNode>,
+        Option<&mut UiTransform>,
+        Option<&mut Transform>,
+    )>,
+) {
+    let Some(physical_scale) = source.physical_scale() else {
+        return;
+    };
+    // World units per virtual pixel, as set up by the camera's scaling mode.
+    let world_per_virtual = letterbox.scale * resolution.width / letterbox.world.width();
+    for (entity, mut tween, node, ui_transform, transform) in tweens.iter_mut() {
+        if tween.is_finished() && !letterbox.is_changed() && !tween.is_changed() {
+            continue;
+        }
+        tween.elapsed += time.delta_secs();
+        let offset = tween.offset();
+
+        if let (Some(node), Some(mut ui_transform)) = (node, ui_transform) {
+            // Physical pixels per UI pixel of this node, including enclosing transforms such
+            // as the HUD's own scale in embedded mode.
+            let parent_scale = parents
+                .get(entity)
+                .and_then(|child_of| globals.get(child_of.parent()))
+                .map_or(1.0, |global| global.matrix2.x_axis.length());
+            let ui_per_virtual =
+                letterbox.scale * physical_scale * node.inverse_scale_factor() / parent_scale;
+            let offset = offset * ui_per_virtual;
+            ui_transform.translation = Val2::px(offset.x, offset.y);
+        } else if let Some(mut transform) = transform {
+            if world_per_virtual.is_finite() {
+                let offset = Vec2::new(offset.x, -offset.y) * world_per_virtual;
+                transform.translation += (offset - tween.applied).extend(0.0);
+                tween.applied = offset;
+            }
+        }
+    }
+}
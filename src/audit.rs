@@ -0,0 +1,306 @@
+use bevy::ecs::system::SystemParam;
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+use bevy::ui::{CalculatedClip, ComputedNode, ComputedUiTargetCamera, UiGlobalTransform, UiStack};
+use bevy::window::PrimaryWindow;
+
+use crate::layout::LayoutSource;
+use crate::{Hud, Letterbox, ViewportCoords};
+
+/// A development tool that checks every interactive HUD element can actually be clicked.
+///
+/// While present, the audit samples a grid of points every `spacing` virtual pixels over the
+/// whole window, hit-tests each one against the UI the same way picking does, and sends a
+/// `HitTestReport`, logging a warning for every problem. Elements with an `Interaction`
+/// component (e.g. buttons) are audited. The resource removes itself once done.
+///
+/// With `window_sizes`, the primary window is resized to each size in turn and audited once
+/// per layout, then restored, catching layouts that break at extreme aspect ratios. A size
+/// the layout doesn't reach within `RESIZE_TIMEOUT_FRAMES` frames (e.g. one the OS clamps)
+/// is audited as it is, with a warning. Sweeping needs the window to drive the layout: in
+/// embedded mode (`AspectRatioContainer`) or with a `SyntheticWindowSize`, only the current
+/// layout is audited.
+///
+/// ```rust,no_run
+/// # use bevy::prelude::*;
+/// # use bevy_aspect_ratio_mask::HitTestAudit;
+/// fn audit(mut commands: Commands) {
+///     commands.insert_resource(HitTestAudit::new(4.0).with_window_sizes([
+///         Vec2::new(800.0, 1200.0),
+///         Vec2::new(2560.0, 720.0),
+///     ]));
+/// }
+/// ```
+#[derive(Resource, Clone, Debug)]
+pub struct HitTestAudit {
+    /// The distance between sampled points, in virtual pixels. Elements smaller than this
+    /// may be reported as unreachable.
+    pub spacing: f32,
+    /// Logical window sizes to audit, in order. Empty audits the current layout only.
+    pub window_sizes: Vec<Vec2>,
+    next: usize,
+    original_size: Option<Vec2>,
+    resizing: bool,
+    waited: u32,
+}
+
+impl HitTestAudit {
+    /// Audits the current layout, sampling every `spacing` virtual pixels.
+    pub fn new(spacing: f32) -> Self {
+        Self {
+            spacing,
+            window_sizes: Vec::new(),
+            next: 0,
+            original_size: None,
+            resizing: false,
+            waited: 0,
+        }
+    }
+
+    /// How many frames the audit waits for the layout to follow a resize.
+    pub const RESIZE_TIMEOUT_FRAMES: u32 = 60;
+
+    /// Audits the layout at each of these logical window sizes instead.
+    pub fn with_window_sizes(mut self, window_sizes: impl IntoIterator<Item = Vec2>) -> Self {
+        self.window_sizes = window_sizes.into_iter().collect();
+        self
+    }
+}
+
+/// The result of auditing one layout with a `HitTestAudit`.
+#[derive(Message, Clone, Debug, PartialEq)]
+pub struct HitTestReport {
+    /// The layout that was audited.
+    pub letterbox: Letterbox,
+    /// Interactive elements that no sampled point reaches.
+    pub unreachable: Vec<Entity>,
+    /// Interactive elements covered, at least partly, by another node that blocks picking.
+    pub overlapped: Vec<HitTestOverlap>,
+}
+
+impl HitTestReport {
+    /// Whether every interactive element is fully reachable.
+    pub fn is_clean(&self) -> bool {
+        self.unreachable.is_empty() && self.overlapped.is_empty()
+    }
+}
+
+/// An interactive element hidden behind another node at some sampled points.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HitTestOverlap {
+    /// The covered interactive element.
+    pub element: Entity,
+    /// The node that blocks picking above it.
+    pub blocker: Entity,
+    /// How many sampled points are blocked.
+    pub points: usize,
+}
+
+/// The UI state a hit test reads.
+#[allow(clippy::type_complexity)]
+#[derive(SystemParam)]
+pub(crate) struct HitTester<'w, 's> {
+    hud: Res<'w, Hud>,
+    letterbox: Res<'w, Letterbox>,
+    coords: ViewportCoords<'w, 's>,
+    ui_stack: Res<'w, UiStack>,
+    nodes: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static ComputedNode,
+            &'static UiGlobalTransform,
+            &'static ComputedUiTargetCamera,
+            Option<&'static InheritedVisibility>,
+            Option<&'static CalculatedClip>,
+            Option<&'static Pickable>,
+            Has<Interaction>,
+        ),
+    >,
+}
+
+impl HitTester<'_, '_> {
+    /// Samples the whole window and reports what each interactive element is reached by.
+    fn audit(&self, spacing: f32) -> HitTestReport {
+        let letterbox = *self.letterbox;
+        let camera = self
+            .nodes
+            .get(self.hud.0)
+            .ok()
+            .and_then(|(_, _, _, target, ..)| target.get());
+
+        // Only visible interactive elements on the HUD's camera can be reached at all.
+        let mut reached: HashMap<Entity, usize> = self
+            .nodes
+            .iter_many(&self.ui_stack.uinodes)
+            .filter(|(_, node, _, target, visibility, _, _, interactive)| {
+                *interactive
+                    && target.get() == camera
+                    && node.size().min_element() > 0.0
+                    && visibility.is_some_and(|visibility| visibility.get())
+            })
+            .map(|(entity, ..)| (entity, 0))
+            .collect();
+        let mut overlaps: HashMap<(Entity, Entity), usize> = HashMap::default();
+
+        let spacing = spacing.max(f32::EPSILON);
+        let count = (letterbox.viewport / spacing).ceil().as_uvec2();
+        for y in 0..count.y {
+            for x in 0..count.x {
+                let point = (Vec2::new(x as f32, y as f32) + 0.5) * spacing - letterbox.bars;
+                let Some(physical) = self.coords.viewport_to_physical(self.hud.0, point) else {
+                    continue;
+                };
+
+                let mut blocker = None;
+                for entity in self.ui_stack.uinodes.iter().rev() {
+                    let Ok((_, node, transform, target, visibility, clip, pickable, interactive)) =
+                        self.nodes.get(*entity)
+                    else {
+                        continue;
+                    };
+                    let hit = target.get() == camera
+                        && visibility.is_some_and(|visibility| visibility.get())
+                        && node.contains_point(*transform, physical)
+                        && clip.is_none_or(|clip| clip.clip.contains(physical));
+                    if !hit {
+                        continue;
+                    }
+
+                    match blocker {
+                        None => {
+                            if let Some(count) = reached.get_mut(entity) {
+                                *count += 1;
+                            }
+                            if pickable.is_none_or(|pickable| pickable.should_block_lower) {
+                                blocker = Some(*entity);
+                            }
+                        }
+                        Some(blocker) if interactive => {
+                            *overlaps.entry((*entity, blocker)).or_default() += 1;
+                        }
+                        Some(_) => {}
+                    }
+                }
+            }
+        }
+
+        let mut unreachable: Vec<Entity> = reached
+            .iter()
+            .filter(|(_, count)| **count == 0)
+            .map(|(entity, _)| *entity)
+            .collect();
+        unreachable.sort();
+        let mut overlapped: Vec<HitTestOverlap> = overlaps
+            .into_iter()
+            .filter(|((element, _), _)| reached.contains_key(element))
+            .map(|((element, blocker), points)| HitTestOverlap {
+                element,
+                blocker,
+                points,
+            })
+            .collect();
+        overlapped.sort_by_key(|overlap| (overlap.element, overlap.blocker));
+
+        HitTestReport {
+            letterbox,
+            unreachable,
+            overlapped,
+        }
+    }
+}
+
+/// Steps a `HitTestAudit` through its window sizes, auditing each settled layout.
+///
+/// Runs after the UI stack is built, so the audit sees this frame's layout.
+pub(crate) fn run_hit_test_audit(
+    mut commands: Commands,
+    mut audit: ResMut<HitTestAudit>,
+    tester: HitTester,
+    source: LayoutSource,
+    windows: Query<(Entity, &Window), With<PrimaryWindow>>,
+    mut reports: MessageWriter<HitTestReport>,
+) {
+    // Resizing the window only changes the layout when the window drives it.
+    let window = windows.single().ok().filter(|_| source.follows_window());
+    if audit.resizing {
+        // Wait for the resize to reach the layout source and the new layout to be applied.
+        let settled = source
+            .size()
+            .is_some_and(|size| tester.letterbox.container_size().distance(size) <= 0.5);
+        if !settled {
+            audit.waited += 1;
+            if audit.waited < HitTestAudit::RESIZE_TIMEOUT_FRAMES {
+                return;
+            }
+            warn!(
+                "Hit-test audit: the layout didn't follow the resize to {:?} within {} frames, \
+                 auditing it as is",
+                audit.window_sizes[audit.next],
+                HitTestAudit::RESIZE_TIMEOUT_FRAMES
+            );
+        }
+        audit.resizing = false;
+    } else if audit.next < audit.window_sizes.len() && audit.original_size.is_none() {
+        let Some((window, current)) = window else {
+            warn!(
+                "Hit-test audit: window sizes are ignored while an AspectRatioContainer or \
+                 SyntheticWindowSize drives the layout, auditing the current layout only"
+            );
+            audit.window_sizes.clear();
+            return;
+        };
+        audit.original_size = Some(current.resolution.size());
+        let size = audit.window_sizes[audit.next];
+        start_resize(&mut commands, &mut audit, window, size);
+        return;
+    }
+
+    let report = tester.audit(audit.spacing);
+    for entity in &report.unreachable {
+        warn!(
+            "Hit-test audit at {:?}: {entity} is unreachable",
+            report.letterbox.container_size()
+        );
+    }
+    for overlap in &report.overlapped {
+        warn!(
+            "Hit-test audit at {:?}: {} is covered by {} at {} points",
+            report.letterbox.container_size(),
+            overlap.element,
+            overlap.blocker,
+            overlap.points
+        );
+    }
+    reports.write(report);
+
+    audit.next += 1;
+    if let Some((window, _)) = window {
+        if let Some(size) = audit.window_sizes.get(audit.next).copied() {
+            start_resize(&mut commands, &mut audit, window, size);
+            return;
+        }
+        if let Some(size) = audit.original_size {
+            resize_window(&mut commands, window, size);
+        }
+    }
+    commands.remove_resource::<HitTestAudit>();
+}
+
+fn start_resize(commands: &mut Commands, audit: &mut HitTestAudit, window: Entity, size: Vec2) {
+    resize_window(commands, window, size);
+    audit.resizing = true;
+    audit.waited = 0;
+}
+
+/// Resizes `window` to a logical `size` once commands are applied.
+///
+/// Deferred because the hit test reads the window while the audit runs.
+fn resize_window(commands: &mut Commands, window: Entity, size: Vec2) {
+    commands.queue(move |world: &mut World| {
+        if let Some(mut window) = world.get_mut::<Window>(window) {
+            window.resolution.set(size.x, size.y);
+        }
+    });
+}
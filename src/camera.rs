@@ -0,0 +1,99 @@
+use bevy::camera::{ScalingMode, Viewport};
+use bevy::prelude::*;
+
+use crate::layout::{LayoutSource, ScreenSimulation};
+use crate::{AspectPreview, AspectRatioContainer, FitPolicy, Letterbox, Resolution};
+
+/// Marker for a 2D camera whose scaling mode is managed by the plugin.
+///
+/// The camera's orthographic projection follows the active `FitPolicy` and `Resolution`:
+/// `ScalingMode::AutoMin` when letterboxing or expanding, `ScalingMode::AutoMax` when covering.
+/// Without this marker, configure the camera's scaling mode yourself.
+///
+/// Under an `AspectPreview` in window mode, the camera's viewport is also restricted to the
+/// simulated screen, so the world is framed as on that screen. The viewport is cleared again
+/// when the preview ends.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct AspectRatioCamera;
+
+impl FitPolicy {
+    /// The orthographic scaling mode that shows the world as this policy expects.
+    pub fn scaling_mode(self, resolution: &Resolution) -> ScalingMode {
+        match self {
+            FitPolicy::Letterbox | FitPolicy::Expand => ScalingMode::AutoMin {
+                min_width: resolution.width,
+                min_height: resolution.height,
+            },
+            FitPolicy::Cover => ScalingMode::AutoMax {
+                max_width: resolution.width,
+                max_height: resolution.height,
+            },
+        }
+    }
+}
+
+pub(crate) fn update_aspect_ratio_cameras(
+    policy: Res<FitPolicy>,
+    resolution: Res<Resolution>,
+    mut cameras: Query<(&mut Projection, Ref<AspectRatioCamera>)>,
+) {
+    let settings_changed = policy.is_changed() || resolution.is_changed();
+    for (mut projection, marker) in cameras.iter_mut() {
+        if !settings_changed && !marker.is_added() {
+            continue;
+        }
+        if let Projection::Orthographic(orthographic) = projection.as_mut() {
+            orthographic.scaling_mode = policy.scaling_mode(&resolution);
+        }
+    }
+}
+
+/// Marks a camera whose viewport was set for an `AspectPreview`, so only that one is cleared.
+#[derive(Component)]
+pub(crate) struct PreviewViewport;
+
+/// Restricts the viewport of managed cameras to the screen simulated by an `AspectPreview`.
+pub(crate) fn update_preview_viewports(
+    mut commands: Commands,
+    preview: Option<Res<AspectPreview>>,
+    container: Option<Res<AspectRatioContainer>>,
+    letterbox: Res<Letterbox>,
+    source: LayoutSource,
+    screen: ScreenSimulation,
+    mut cameras: Query<(Entity, &mut Camera, Has<PreviewViewport>), With<AspectRatioCamera>>,
+) {
+    // In embedded mode the camera isn't tied to the container, so the preview only frames
+    // the HUD.
+    let viewport = match (preview, container, source.physical_scale()) {
+        (Some(_), None, Some(physical_scale)) => {
+            let window_size = letterbox.container_size();
+            let screen_size = screen.screen_size(window_size);
+            let position = (window_size - screen_size) / 2.0 * physical_scale;
+            Some(Viewport {
+                physical_position: position.round().as_uvec2(),
+                physical_size: (screen_size * physical_scale)
+                    .round()
+                    .as_uvec2()
+                    .max(UVec2::ONE),
+                ..default()
+            })
+        }
+        _ => None,
+    };
+
+    for (entity, mut camera, previewed) in cameras.iter_mut() {
+        match &viewport {
+            Some(viewport) => {
+                camera.viewport = Some(viewport.clone());
+                if !previewed {
+                    commands.entity(entity).insert(PreviewViewport);
+                }
+            }
+            None if previewed => {
+                camera.viewport = None;
+                commands.entity(entity).remove::<PreviewViewport>();
+            }
+            None => {}
+        }
+    }
+}
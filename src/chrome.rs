@@ -0,0 +1,263 @@
+use bevy::picking::pointer::PointerButton;
+use bevy::prelude::*;
+use bevy::window::{PrimaryWindow, WindowCloseRequested};
+
+use crate::{AspectRatioMaskSide, MaskRegions, WINDOW_CONTROLS_Z};
+
+/// Lets the player move a borderless window by dragging the bars.
+///
+/// While this resource is present, pressing `button` on one of `sides` (or on content attached
+/// to it that doesn't stop pointer propagation) starts an OS window drag, as a title bar would.
+/// The drag is handed to the windowing backend, so it follows the OS's own snapping and
+/// multi-monitor behavior. Only bars that are visible can be dragged: nothing happens under
+/// `FitPolicy::Cover` or `FitPolicy::Expand`, or when the window matches the resolution.
+///
+/// Drags always move the primary window.
+///
+/// ```rust,no_run
+/// # use bevy::prelude::*;
+/// # use bevy_aspect_ratio_mask::BarDragRegions;
+/// # let mut app = App::new();
+/// app.insert_resource(BarDragRegions::default());
+/// ```
+#[derive(Resource, Clone, Debug, PartialEq)]
+pub struct BarDragRegions {
+    /// The bars that act as drag regions.
+    pub sides: Vec<AspectRatioMaskSide>,
+    /// The pointer button that starts a drag.
+    pub button: PointerButton,
+}
+
+impl Default for BarDragRegions {
+    fn default() -> Self {
+        Self {
+            sides: AspectRatioMaskSide::ALL.to_vec(),
+            button: PointerButton::Primary,
+        }
+    }
+}
+
+/// Starts a window drag when a drag-enabled bar is pressed.
+pub(crate) fn drag_window_from_bars(
+    press: On<Pointer<Press>>,
+    regions: Option<Res<BarDragRegions>>,
+    bars: Query<&AspectRatioMaskSide>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    let Some(regions) = regions else {
+        return;
+    };
+    let Ok(side) = bars.get(press.entity) else {
+        return;
+    };
+    if press.button != regions.button || !regions.sides.contains(side) {
+        return;
+    }
+    if let Ok(mut window) = windows.single_mut() {
+        window.start_drag_move();
+    }
+}
+
+/// Minimize, maximize and close buttons drawn in one of the bars of a borderless window.
+///
+/// While this resource is present the buttons are spawned in the top-right corner of `side`,
+/// so games with custom chrome don't lose screen rows to a title bar. They are drawn and
+/// hit-tested above every `HudLayer`, including modal shields, and pressing them never starts
+/// a `BarDragRegions` drag. Closing sends `WindowCloseRequested`, so the game can intercept it.
+/// Like `BarDragRegions`, the buttons act on the primary window.
+///
+/// The buttons are sized in the bar's UI pixels (virtual pixels in window mode) and clipped
+/// by the bar, so they are only fully visible while the bar is at least `button_size.y` thick.
+/// Changing this resource rebuilds them; removing it despawns them.
+#[derive(Resource, Clone, Debug, PartialEq)]
+pub struct WindowControls {
+    /// The bar holding the buttons.
+    pub side: AspectRatioMaskSide,
+    /// The size of each button.
+    pub button_size: Vec2,
+    /// The color of the button icons.
+    pub icon_color: Color,
+    /// The background of a hovered button.
+    pub hover_color: Color,
+    /// The background of the hovered close button.
+    pub close_hover_color: Color,
+}
+
+impl Default for WindowControls {
+    fn default() -> Self {
+        Self {
+            side: AspectRatioMaskSide::Top,
+            button_size: Vec2::new(46.0, 32.0),
+            icon_color: Color::WHITE,
+            hover_color: Color::srgba(1.0, 1.0, 1.0, 0.1),
+            close_hover_color: Color::srgb(0.77, 0.17, 0.11),
+        }
+    }
+}
+
+/// A window button spawned by `WindowControls`.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum WindowControl {
+    Minimize,
+    Maximize,
+    Close,
+}
+
+/// The row holding the `WindowControls` buttons.
+#[derive(Component)]
+pub(crate) struct WindowControlsRow;
+
+/// Rebuilds the window buttons when `WindowControls` changes.
+pub(crate) fn sync_window_controls(
+    mut commands: Commands,
+    controls: Option<Res<WindowControls>>,
+    regions: Option<Res<MaskRegions>>,
+    rows: Query<Entity, With<WindowControlsRow>>,
+) {
+    for row in rows.iter() {
+        commands.entity(row).despawn();
+    }
+    let (Some(controls), Some(regions)) = (controls, regions) else {
+        return;
+    };
+
+    let row = commands
+        .spawn((
+            Name::new("Window Controls"),
+            WindowControlsRow,
+            Node {
+                position_type: PositionType::Absolute,
+                top: Val::Px(0.0),
+                right: Val::Px(0.0),
+                ..default()
+            },
+            GlobalZIndex(WINDOW_CONTROLS_Z),
+            Pickable::IGNORE,
+            ChildOf(regions.get(controls.side)),
+        ))
+        .id();
+    for control in [
+        WindowControl::Minimize,
+        WindowControl::Maximize,
+        WindowControl::Close,
+    ] {
+        commands
+            .spawn((
+                control,
+                Button,
+                Node {
+                    width: Val::Px(controls.button_size.x),
+                    height: Val::Px(controls.button_size.y),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..default()
+                },
+                BackgroundColor(Color::NONE),
+                ChildOf(row),
+            ))
+            .with_children(|button| spawn_icon(button, control, controls.icon_color));
+    }
+}
+
+/// Draws a button's icon with plain nodes, so it doesn't depend on a font's glyph coverage.
+fn spawn_icon(button: &mut ChildSpawnerCommands, control: WindowControl, color: Color) {
+    const ICON: f32 = 10.0;
+    let line = |angle: f32| {
+        (
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Px(ICON * std::f32::consts::SQRT_2),
+                height: Val::Px(1.0),
+                ..default()
+            },
+            UiTransform::from_rotation(Rot2::radians(angle)),
+            BackgroundColor(color),
+            Pickable::IGNORE,
+        )
+    };
+    match control {
+        WindowControl::Minimize => {
+            button.spawn((
+                Node {
+                    width: Val::Px(ICON),
+                    height: Val::Px(1.0),
+                    ..default()
+                },
+                BackgroundColor(color),
+                Pickable::IGNORE,
+            ));
+        }
+        WindowControl::Maximize => {
+            button.spawn((
+                Node {
+                    width: Val::Px(ICON),
+                    height: Val::Px(ICON),
+                    border: UiRect::all(Val::Px(1.0)),
+                    ..default()
+                },
+                BorderColor::all(color),
+                Pickable::IGNORE,
+            ));
+        }
+        WindowControl::Close => {
+            button.spawn(line(std::f32::consts::FRAC_PI_4));
+            button.spawn(line(-std::f32::consts::FRAC_PI_4));
+        }
+    }
+}
+
+/// Highlights hovered window buttons.
+pub(crate) fn highlight_window_controls(
+    controls: Option<Res<WindowControls>>,
+    mut buttons: Query<(&WindowControl, &Interaction, &mut BackgroundColor), Changed<Interaction>>,
+) {
+    let Some(controls) = controls else {
+        return;
+    };
+    for (control, interaction, mut background) in buttons.iter_mut() {
+        background.0 = match (interaction, control) {
+            (Interaction::None, _) => Color::NONE,
+            (_, WindowControl::Close) => controls.close_hover_color,
+            _ => controls.hover_color,
+        };
+    }
+}
+
+/// Keeps presses on window buttons from reaching the bar, where they would start a drag.
+pub(crate) fn stop_window_control_press(
+    mut press: On<Pointer<Press>>,
+    controls: Query<(), With<WindowControl>>,
+) {
+    if controls.contains(press.entity) {
+        press.propagate(false);
+    }
+}
+
+/// Minimizes, maximizes or closes the primary window when its button is clicked.
+pub(crate) fn click_window_control(
+    click: On<Pointer<Click>>,
+    controls: Query<&WindowControl>,
+    mut windows: Query<(Entity, &mut Window), With<PrimaryWindow>>,
+    mut close_requested: MessageWriter<WindowCloseRequested>,
+    mut maximized: Local<bool>,
+) {
+    let Ok(control) = controls.get(click.entity) else {
+        return;
+    };
+    let Ok((entity, mut window)) = windows.single_mut() else {
+        return;
+    };
+    if click.button != PointerButton::Primary {
+        return;
+    }
+    match control {
+        WindowControl::Minimize => window.set_minimized(true),
+        WindowControl::Maximize => {
+            *maximized = !*maximized;
+            window.set_maximized(*maximized);
+        }
+        WindowControl::Close => {
+            close_requested.write(WindowCloseRequested { window: entity });
+        }
+    }
+}
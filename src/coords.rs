@@ -0,0 +1,176 @@
+use bevy::ecs::system::SystemParam;
+use bevy::math::Affine2;
+use bevy::prelude::*;
+use bevy::ui::{ComputedNode, UiGlobalTransform};
+use bevy::window::PrimaryWindow;
+
+/// Maps positions between the window and the virtual pixels of a HUD or `NestedViewport`.
+///
+/// The mapping goes through the node's `UiGlobalTransform`, which already composes every
+/// enclosing letterbox, `UiTransform` and nesting level, so the same calls work for the
+/// top-level `Hud` and for viewports nested at any depth.
+#[derive(SystemParam)]
+pub struct ViewportCoords<'w, 's> {
+    nodes: Query<'w, 's, (&'static ComputedNode, &'static UiGlobalTransform)>,
+    primary_window: Query<'w, 's, &'static Window, With<PrimaryWindow>>,
+}
+
+impl ViewportCoords<'_, '_> {
+    /// The affine map from physical render-target positions to `viewport`'s virtual pixels,
+    /// with the origin at its top-left corner.
+    pub fn physical_to_viewport_affine(&self, viewport: Entity) -> Option<Affine2> {
+        let (node, transform) = self.nodes.get(viewport).ok()?;
+        let inverse_scale_factor = node.inverse_scale_factor();
+        Some(
+            Affine2::from_translation(node.size() * inverse_scale_factor / 2.0)
+                * Affine2::from_scale(Vec2::splat(inverse_scale_factor))
+                * transform.try_inverse()?,
+        )
+    }
+
+    /// The affine map from logical primary-window positions to `viewport`'s virtual pixels.
+    pub fn window_to_viewport_affine(&self, viewport: Entity) -> Option<Affine2> {
+        let scale_factor = self.primary_window.single().ok()?.scale_factor();
+        Some(
+            self.physical_to_viewport_affine(viewport)?
+                * Affine2::from_scale(Vec2::splat(scale_factor)),
+        )
+    }
+
+    /// Maps a physical render-target position into `viewport`'s virtual pixels,
+    /// with the origin at its top-left corner.
+    pub fn physical_to_viewport(&self, viewport: Entity, physical: Vec2) -> Option<Vec2> {
+        Some(
+            self.physical_to_viewport_affine(viewport)?
+                .transform_point2(physical),
+        )
+    }
+
+    /// Maps a point in `viewport`'s virtual pixels to a physical render-target position.
+    pub fn viewport_to_physical(&self, viewport: Entity, point: Vec2) -> Option<Vec2> {
+        Some(
+            self.physical_to_viewport_affine(viewport)?
+                .inverse()
+                .transform_point2(point),
+        )
+    }
+
+    /// Maps a logical primary-window position (e.g. from `Window::cursor_position`) into
+    /// `viewport`'s virtual pixels.
+    pub fn window_to_viewport(&self, viewport: Entity, position: Vec2) -> Option<Vec2> {
+        Some(
+            self.window_to_viewport_affine(viewport)?
+                .transform_point2(position),
+        )
+    }
+
+    /// Maps a point in `viewport`'s virtual pixels to a logical primary-window position.
+    pub fn viewport_to_window(&self, viewport: Entity, point: Vec2) -> Option<Vec2> {
+        Some(
+            self.window_to_viewport_affine(viewport)?
+                .inverse()
+                .transform_point2(point),
+        )
+    }
+
+    /// The primary window's cursor in `viewport`'s virtual pixels, if the cursor is in the window.
+    pub fn cursor_to_viewport(&self, viewport: Entity) -> Option<Vec2> {
+        let window = self.primary_window.single().ok()?;
+        self.physical_to_viewport(viewport, window.physical_cursor_position()?)
+    }
+
+    /// Builds a chain starting at logical window positions and going through each of
+    /// `viewports` in order, e.g. `[hud, cabinet_screen]` for window → outer → inner.
+    pub fn chain(&self, viewports: &[Entity]) -> Option<CoordinateChain> {
+        viewports
+            .iter()
+            .try_fold(CoordinateChain::window(), |chain, viewport| {
+                Some(chain.then(self.window_to_viewport_affine(*viewport)?))
+            })
+    }
+}
+
+/// A composable chain of coordinate spaces, e.g. window → outer virtual → inner virtual →
+/// world.
+///
+/// Each space is stored as the affine map from logical window positions into it, so any two
+/// spaces of the chain can be mapped into each other, in either direction. Space `0` is always
+/// the window itself. Build one with `ViewportCoords::chain` and extend it with `then_world`
+/// to reach a 2D camera's world space.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CoordinateChain {
+    spaces: Vec<Affine2>,
+}
+
+impl CoordinateChain {
+    /// A chain holding only the logical window space.
+    pub fn window() -> Self {
+        Self {
+            spaces: vec![Affine2::IDENTITY],
+        }
+    }
+
+    /// Appends a space given the affine map from logical window positions into it.
+    pub fn then(mut self, from_window: Affine2) -> Self {
+        self.spaces.push(from_window);
+        self
+    }
+
+    /// Appends the world space of a 2D camera that renders to the whole window.
+    ///
+    /// Returns `None` if the camera can't map window positions to its world plane.
+    #[cfg(feature = "camera")]
+    pub fn then_world(self, camera: &Camera, camera_transform: &GlobalTransform) -> Option<Self> {
+        // An orthographic 2D projection is affine, so three points define it.
+        let origin = camera
+            .viewport_to_world_2d(camera_transform, Vec2::ZERO)
+            .ok()?;
+        let x = camera
+            .viewport_to_world_2d(camera_transform, Vec2::X)
+            .ok()?
+            - origin;
+        let y = camera
+            .viewport_to_world_2d(camera_transform, Vec2::Y)
+            .ok()?
+            - origin;
+        Some(self.then(Affine2::from_cols(x, y, origin)))
+    }
+
+    /// The number of spaces in the chain, including the window.
+    pub fn len(&self) -> usize {
+        self.spaces.len()
+    }
+
+    /// Always `false`: a chain starts with the window space.
+    pub fn is_empty(&self) -> bool {
+        self.spaces.is_empty()
+    }
+
+    /// The affine map from space `from` to space `to`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either index is out of bounds.
+    pub fn between(&self, from: usize, to: usize) -> Affine2 {
+        self.spaces[to] * self.spaces[from].inverse()
+    }
+
+    /// Maps `point` from space `from` to space `to`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either index is out of bounds.
+    pub fn map(&self, point: Vec2, from: usize, to: usize) -> Vec2 {
+        self.between(from, to).transform_point2(point)
+    }
+
+    /// Maps a logical window position into the last space of the chain.
+    pub fn from_window(&self, position: Vec2) -> Vec2 {
+        self.map(position, 0, self.len() - 1)
+    }
+
+    /// Maps a point in the last space of the chain back to a logical window position.
+    pub fn to_window(&self, point: Vec2) -> Vec2 {
+        self.map(point, self.len() - 1, 0)
+    }
+}
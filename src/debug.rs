@@ -0,0 +1,429 @@
+use bevy::prelude::*;
+
+use crate::{Hud, Letterbox, ViewportCoords, DEBUG_OVERLAY_Z};
+
+/// A debug overlay showing the virtual grid, the safe area and the bars.
+///
+/// Insert this resource to draw the overlay above everything else, and remove it to hide it.
+/// The overlay follows the current `Letterbox` and ignores picking, so it can stay on while
+/// testing. Pick a colorblind-safe `palette` and keep `hatching` on so regions stay
+/// distinguishable by pattern as well as by color.
+///
+/// For aligning HUD art to design specs, the overlay can draw rulers in virtual pixels along
+/// the top and left edges of the virtual area, and dragging with `measure_button` measures
+/// the distance between two points in virtual pixels. The last measurement stays on screen,
+/// with its readout, until the next one, and is published in `DebugMeasurement`.
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct DebugOverlay {
+    /// The colors of the overlay.
+    pub palette: DebugPalette,
+    /// Fill the bars with diagonal hatching, not just an outline.
+    pub hatching: bool,
+    /// The distance between grid lines, in virtual pixels. Zero hides the grid.
+    pub grid_spacing: f32,
+    /// Draw rulers along the top and left edges of the virtual area.
+    pub rulers: bool,
+    /// The mouse button that drag-measures distances. `None` disables measuring.
+    pub measure_button: Option<MouseButton>,
+}
+
+impl Default for DebugOverlay {
+    fn default() -> Self {
+        Self {
+            palette: DebugPalette::default(),
+            hatching: true,
+            grid_spacing: 64.0,
+            rulers: true,
+            measure_button: Some(MouseButton::Right),
+        }
+    }
+}
+
+/// The color scheme of the `DebugOverlay`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DebugPalette {
+    /// Red, green and blue. Not distinguishable for most color-vision deficiencies.
+    #[default]
+    Standard,
+    /// The Okabe–Ito palette, safe for protanopia, deuteranopia and tritanopia.
+    OkabeIto,
+    /// Paul Tol's bright palette, safe for the common color-vision deficiencies.
+    TolBright,
+    /// Black, white and gray only, relying on line weight and hatching.
+    Monochrome,
+}
+
+/// The colors used for each region of the `DebugOverlay`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DebugColors {
+    /// The virtual grid lines.
+    pub grid: Color,
+    /// The outline of the safe (virtual) area.
+    pub safe_area: Color,
+    /// The outline and hatching of the bars.
+    pub bars: Color,
+}
+
+impl DebugPalette {
+    /// The colors of this palette.
+    pub fn colors(self) -> DebugColors {
+        match self {
+            DebugPalette::Standard => DebugColors {
+                grid: Color::srgba(0.0, 0.4, 1.0, 0.35),
+                safe_area: Color::srgb(0.0, 0.9, 0.2),
+                bars: Color::srgb(1.0, 0.1, 0.1),
+            },
+            DebugPalette::OkabeIto => DebugColors {
+                grid: Color::srgba_u8(0x56, 0xB4, 0xE9, 0x60),
+                safe_area: Color::srgb_u8(0xE6, 0x9F, 0x00),
+                bars: Color::srgb_u8(0xCC, 0x79, 0xA7),
+            },
+            DebugPalette::TolBright => DebugColors {
+                grid: Color::srgba_u8(0x44, 0x77, 0xAA, 0x60),
+                safe_area: Color::srgb_u8(0xCC, 0xBB, 0x44),
+                bars: Color::srgb_u8(0xEE, 0x66, 0x77),
+            },
+            DebugPalette::Monochrome => DebugColors {
+                grid: Color::srgba(1.0, 1.0, 1.0, 0.25),
+                safe_area: Color::WHITE,
+                bars: Color::srgb(0.6, 0.6, 0.6),
+            },
+        }
+    }
+}
+
+/// The last distance measured with the `DebugOverlay`, in the HUD's virtual pixels.
+///
+/// Present once a first measurement was started.
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct DebugMeasurement {
+    /// Where the drag started.
+    pub start: Vec2,
+    /// Where the drag is, or ended.
+    pub end: Vec2,
+}
+
+impl DebugMeasurement {
+    /// The horizontal and vertical distance, in virtual pixels.
+    pub fn delta(&self) -> Vec2 {
+        self.end - self.start
+    }
+
+    /// The straight-line distance, in virtual pixels.
+    pub fn distance(&self) -> f32 {
+        self.start.distance(self.end)
+    }
+}
+
+/// The root node of the spawned `DebugOverlay`.
+#[derive(Component)]
+pub(crate) struct DebugOverlayRoot;
+
+/// The width of the safe area and bar outlines, in virtual pixels.
+const OUTLINE_WIDTH: f32 = 2.0;
+/// The distance between hatching stripes, in virtual pixels.
+const HATCH_SPACING: f32 = 12.0;
+/// The distance between minor ruler ticks, in virtual pixels.
+const RULER_TICK_SPACING: f32 = 8.0;
+/// The distance between labeled ruler ticks when the grid is hidden, in virtual pixels.
+const RULER_LABEL_SPACING: f32 = 64.0;
+/// The font size of ruler labels and the measurement readout, in virtual pixels.
+const LABEL_FONT_SIZE: f32 = 10.0;
+
+/// The root node of the drawn `DebugMeasurement`.
+#[derive(Component)]
+pub(crate) struct DebugMeasurementRoot;
+
+/// Rebuilds the overlay when it is toggled or changed, or the layout changes.
+pub(crate) fn update_debug_overlay(
+    mut commands: Commands,
+    overlay: Option<Res<DebugOverlay>>,
+    letterbox: Res<Letterbox>,
+    hud: Res<Hud>,
+    roots: Query<Entity, With<DebugOverlayRoot>>,
+) {
+    for root in roots.iter() {
+        commands.entity(root).despawn();
+    }
+    let Some(overlay) = overlay else {
+        return;
+    };
+    let colors = overlay.palette.colors();
+    let size = letterbox.virtual_size();
+    let bars = letterbox.bars;
+    let viewport = letterbox.viewport;
+
+    // The root covers the whole viewport; its origin is the top-left of the window.
+    let root = commands
+        .spawn((
+            DebugOverlayRoot,
+            Name::new("Aspect Ratio Debug Overlay"),
+            rect_node(-bars, viewport),
+            GlobalZIndex(DEBUG_OVERLAY_Z),
+            Pickable::IGNORE,
+            ChildOf(hud.0),
+        ))
+        .id();
+
+    if overlay.grid_spacing > 0.0 {
+        let lines = (size / overlay.grid_spacing).ceil().as_uvec2();
+        for x in 1..lines.x {
+            let left = bars.x + x as f32 * overlay.grid_spacing - 0.5;
+            commands.spawn((
+                rect_node(Vec2::new(left, bars.y), Vec2::new(1.0, size.y)),
+                BackgroundColor(colors.grid),
+                Pickable::IGNORE,
+                ChildOf(root),
+            ));
+        }
+        for y in 1..lines.y {
+            let top = bars.y + y as f32 * overlay.grid_spacing - 0.5;
+            commands.spawn((
+                rect_node(Vec2::new(bars.x, top), Vec2::new(size.x, 1.0)),
+                BackgroundColor(colors.grid),
+                Pickable::IGNORE,
+                ChildOf(root),
+            ));
+        }
+    }
+
+    commands.spawn((
+        Node {
+            border: UiRect::all(Val::Px(OUTLINE_WIDTH)),
+            ..rect_node(bars, size)
+        },
+        BorderColor::all(colors.safe_area),
+        Pickable::IGNORE,
+        ChildOf(root),
+    ));
+
+    if overlay.rulers {
+        let label_spacing = if overlay.grid_spacing > 0.0 {
+            overlay.grid_spacing
+        } else {
+            RULER_LABEL_SPACING
+        };
+        spawn_rulers(
+            &mut commands,
+            root,
+            bars,
+            size,
+            label_spacing,
+            colors.safe_area,
+        );
+    }
+
+    let regions = [
+        (Vec2::ZERO, Vec2::new(bars.x, viewport.y)),
+        (
+            Vec2::new(viewport.x - bars.x, 0.0),
+            Vec2::new(bars.x, viewport.y),
+        ),
+        (Vec2::new(bars.x, 0.0), Vec2::new(size.x, bars.y)),
+        (
+            Vec2::new(bars.x, viewport.y - bars.y),
+            Vec2::new(size.x, bars.y),
+        ),
+    ];
+    for (position, region) in regions {
+        if region.min_element() <= 0.0 {
+            continue;
+        }
+        let bar = commands
+            .spawn((
+                Node {
+                    border: UiRect::all(Val::Px(OUTLINE_WIDTH)),
+                    overflow: Overflow::clip(),
+                    ..rect_node(position, region)
+                },
+                BorderColor::all(colors.bars),
+                Pickable::IGNORE,
+                ChildOf(root),
+            ))
+            .id();
+        if overlay.hatching {
+            spawn_hatching(&mut commands, bar, region, colors.bars.with_alpha(0.5));
+        }
+    }
+}
+
+/// Draws rulers along the top and left edges of the virtual area at `origin`, with a labeled
+/// tick every `label_spacing` virtual pixels.
+fn spawn_rulers(
+    commands: &mut Commands,
+    parent: Entity,
+    origin: Vec2,
+    size: Vec2,
+    label_spacing: f32,
+    color: Color,
+) {
+    let every = (label_spacing / RULER_TICK_SPACING).round().max(1.0) as u32;
+    for (axis, extent) in [(Vec2::X, size.x), (Vec2::Y, size.y)] {
+        let across = Vec2::ONE - axis;
+        let ticks = (extent / RULER_TICK_SPACING).floor() as u32;
+        for i in 1..=ticks {
+            let distance = i as f32 * RULER_TICK_SPACING;
+            let labeled = i % every == 0;
+            let length = if labeled { 10.0 } else { 4.0 };
+            commands.spawn((
+                rect_node(origin + axis * (distance - 0.5), axis + across * length),
+                BackgroundColor(color),
+                Pickable::IGNORE,
+                ChildOf(parent),
+            ));
+            if labeled {
+                spawn_label(
+                    commands,
+                    parent,
+                    origin + axis * (distance + 2.0) + across * length,
+                    format!("{distance}"),
+                    color,
+                );
+            }
+        }
+    }
+}
+
+/// Draws a small text label with its top-left corner at `position`.
+fn spawn_label(
+    commands: &mut Commands,
+    parent: Entity,
+    position: Vec2,
+    text: String,
+    color: Color,
+) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(position.x),
+            top: Val::Px(position.y),
+            ..default()
+        },
+        Text::new(text),
+        TextFont::from_font_size(LABEL_FONT_SIZE),
+        TextColor(color),
+        Pickable::IGNORE,
+        ChildOf(parent),
+    ));
+}
+
+/// Tracks drag-measuring with the overlay's `measure_button`.
+pub(crate) fn measure_debug_distance(
+    mut commands: Commands,
+    overlay: Res<DebugOverlay>,
+    buttons: Option<Res<ButtonInput<MouseButton>>>,
+    hud: Res<Hud>,
+    coords: ViewportCoords,
+    measurement: Option<ResMut<DebugMeasurement>>,
+) {
+    let (Some(button), Some(buttons)) = (overlay.measure_button, buttons) else {
+        return;
+    };
+    let Some(cursor) = coords.cursor_to_viewport(hud.0) else {
+        return;
+    };
+    if buttons.just_pressed(button) {
+        commands.insert_resource(DebugMeasurement {
+            start: cursor,
+            end: cursor,
+        });
+    } else if let Some(mut measurement) = measurement.filter(|_| buttons.pressed(button)) {
+        if measurement.end != cursor {
+            measurement.end = cursor;
+        }
+    }
+}
+
+/// Redraws the measurement line and its readout.
+pub(crate) fn update_debug_measurement(
+    mut commands: Commands,
+    overlay: Option<Res<DebugOverlay>>,
+    measurement: Option<Res<DebugMeasurement>>,
+    hud: Res<Hud>,
+    roots: Query<Entity, With<DebugMeasurementRoot>>,
+) {
+    for root in roots.iter() {
+        commands.entity(root).despawn();
+    }
+    let (Some(overlay), Some(measurement)) = (overlay, measurement) else {
+        return;
+    };
+    let color = overlay.palette.colors().safe_area;
+
+    // The root's origin is the top-left of the virtual area, like the measured points.
+    let root = commands
+        .spawn((
+            DebugMeasurementRoot,
+            Name::new("Aspect Ratio Debug Measurement"),
+            rect_node(Vec2::ZERO, Vec2::ZERO),
+            GlobalZIndex(DEBUG_OVERLAY_Z),
+            Pickable::IGNORE,
+            ChildOf(hud.0),
+        ))
+        .id();
+
+    let distance = measurement.distance();
+    let center = (measurement.start + measurement.end) / 2.0;
+    commands.spawn((
+        rect_node(
+            center - Vec2::new(distance, 1.0) / 2.0,
+            Vec2::new(distance, 1.0),
+        ),
+        UiTransform::from_rotation(Rot2::radians(measurement.delta().to_angle())),
+        BackgroundColor(color),
+        Pickable::IGNORE,
+        ChildOf(root),
+    ));
+    for point in [measurement.start, measurement.end] {
+        commands.spawn((
+            rect_node(point - Vec2::splat(2.0), Vec2::splat(4.0)),
+            BackgroundColor(color),
+            Pickable::IGNORE,
+            ChildOf(root),
+        ));
+    }
+    let delta = measurement.delta();
+    spawn_label(
+        &mut commands,
+        root,
+        measurement.end + Vec2::splat(6.0),
+        format!(
+            "{:.0} × {:.0} ({distance:.1} px)",
+            delta.x.abs(),
+            delta.y.abs()
+        ),
+        color,
+    );
+}
+
+/// Fills a `size` region with 45° stripes, clipped by `parent`.
+fn spawn_hatching(commands: &mut Commands, parent: Entity, size: Vec2, color: Color) {
+    let length = (size.x + size.y) * std::f32::consts::SQRT_2;
+    let stripes = ((size.x + size.y) / HATCH_SPACING).ceil() as u32;
+    for i in 0..=stripes {
+        // Stripes cross the vertical center line from `-size.y / 2` to `size.x + size.y / 2`.
+        let center = Vec2::new(i as f32 * HATCH_SPACING - size.y / 2.0, size.y / 2.0);
+        commands.spawn((
+            rect_node(
+                center - Vec2::new(OUTLINE_WIDTH, length) / 2.0,
+                Vec2::new(OUTLINE_WIDTH, length),
+            ),
+            UiTransform::from_rotation(Rot2::degrees(45.0)),
+            BackgroundColor(color),
+            Pickable::IGNORE,
+            ChildOf(parent),
+        ));
+    }
+}
+
+/// An absolutely positioned node covering `size` virtual pixels at `position`.
+fn rect_node(position: Vec2, size: Vec2) -> Node {
+    Node {
+        position_type: PositionType::Absolute,
+        left: Val::Px(position.x),
+        top: Val::Px(position.y),
+        width: Val::Px(size.x),
+        height: Val::Px(size.y),
+        ..default()
+    }
+}
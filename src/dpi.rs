@@ -0,0 +1,90 @@
+use bevy::prelude::*;
+
+use crate::layout::LayoutSource;
+use crate::Letterbox;
+
+/// Millimeters per inch.
+const MM_PER_INCH: f32 = 25.4;
+
+/// The screen density assumed at a scale factor of 1, in physical pixels per inch.
+///
+/// This is the convention most desktop platforms derive their scale factor from.
+const BASELINE_PPI: f32 = 96.0;
+
+/// The actual density of the screen, in physical pixels per inch.
+///
+/// Winit doesn't report physical monitor sizes, so by default `EffectiveDpi` estimates the
+/// density from the window's scale factor. Insert this resource when the real value is known,
+/// e.g. from a platform API or a calibration screen.
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct ScreenPpi(pub f32);
+
+/// How large a virtual pixel is on the player's screen.
+///
+/// Updated by the plugin every frame. Use it to decide between touch-sized and mouse-sized
+/// hit targets: a button that is comfortable to click on a monitor can be far too small for a
+/// finger on a phone showing the same virtual resolution.
+///
+/// ```rust,no_run
+/// # use bevy::prelude::*;
+/// # use bevy_aspect_ratio_mask::EffectiveDpi;
+/// fn button_height(dpi: Res<EffectiveDpi>) -> f32 {
+///     // Fingers need about 9 mm; keep the designed 24 virtual pixels when that's enough.
+///     dpi.virtual_px_for_mm(9.0).max(24.0)
+/// }
+/// ```
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq)]
+pub struct EffectiveDpi {
+    /// Physical pixels per virtual pixel.
+    pub physical_per_virtual: f32,
+    /// The screen's density in physical pixels per inch: `ScreenPpi` if present, otherwise
+    /// an estimate from the window's scale factor.
+    pub screen_ppi: f32,
+    /// Whether `screen_ppi` comes from `ScreenPpi` rather than an estimate.
+    pub measured: bool,
+}
+
+impl EffectiveDpi {
+    /// Virtual pixels per physical inch on screen.
+    pub fn virtual_ppi(&self) -> f32 {
+        if self.physical_per_virtual > 0.0 {
+            self.screen_ppi / self.physical_per_virtual
+        } else {
+            0.0
+        }
+    }
+
+    /// The number of virtual pixels spanning `mm` millimeters on screen.
+    pub fn virtual_px_for_mm(&self, mm: f32) -> f32 {
+        mm / MM_PER_INCH * self.virtual_ppi()
+    }
+
+    /// The on-screen size of `virtual_px` virtual pixels, in millimeters.
+    pub fn mm_for_virtual_px(&self, virtual_px: f32) -> f32 {
+        match self.virtual_ppi() {
+            0.0 => 0.0,
+            ppi => virtual_px / ppi * MM_PER_INCH,
+        }
+    }
+}
+
+pub(crate) fn update_effective_dpi(
+    source: LayoutSource,
+    letterbox: Res<Letterbox>,
+    screen_ppi: Option<Res<ScreenPpi>>,
+    mut dpi: ResMut<EffectiveDpi>,
+) {
+    let Some(physical_scale) = source.physical_scale() else {
+        return;
+    };
+    let (screen_ppi, measured) = match screen_ppi {
+        Some(screen_ppi) => (screen_ppi.0, true),
+        // Physical pixels per container pixel: the window's scale factor.
+        None => (physical_scale * BASELINE_PPI, false),
+    };
+    dpi.set_if_neq(EffectiveDpi {
+        physical_per_virtual: letterbox.scale * physical_scale,
+        screen_ppi,
+        measured,
+    });
+}
@@ -0,0 +1,195 @@
+use bevy::prelude::*;
+use crossbeam_channel::{Receiver, Sender};
+
+use crate::{
+    AspectRatioContainer, DebugOverlay, DesiredUiScale, FitPolicy, LayoutSnapshot, Letterbox,
+    Resolution,
+};
+
+/// A command sent by an external editor to the running game.
+#[derive(Clone, Debug, PartialEq)]
+pub enum EditorCommand {
+    /// Replaces the virtual resolution.
+    SetResolution(Resolution),
+    /// Replaces the fit policy.
+    SetFitPolicy(FitPolicy),
+    /// Moves the preview's top-left corner to a position in logical window pixels.
+    MovePreview(Vec2),
+    /// Resizes the preview to a size in logical window pixels.
+    ResizePreview(Vec2),
+    /// Removes the preview, letterboxing into the window (or the previous container) again.
+    ResetPreview,
+    /// Shows the debug overlay with these settings, or hides it with `None`.
+    SetDebugOverlay(Option<DebugOverlay>),
+    /// Asks for an `EditorEvent::Snapshot` of the current layout.
+    RequestSnapshot,
+}
+
+/// A message sent by the game back to the external editor.
+#[derive(Clone, Debug, PartialEq)]
+pub enum EditorEvent {
+    /// The layout after the commands received so far were applied.
+    Snapshot(LayoutSnapshot),
+}
+
+/// The game's end of the editor channel.
+///
+/// Create it with `EditorBridge::new`, insert it as a resource, and hand the `EditorHandle` to
+/// whatever talks to the editor, usually a thread relaying a socket or pipe from the editor
+/// process. Commands are applied once per frame, in order.
+///
+/// Moving or resizing the preview letterboxes the game into a plugin-managed node instead of
+/// the whole window, using the embedded mode of `AspectRatioContainer`. Embedded mode scales
+/// the HUD with a transform, so while the preview exists the UI scale the plugin drives
+/// (`UiScale`, or `DesiredUiScale` in cooperative mode) is reset to 1, keeping the preview's
+/// position and size in logical window pixels; it is restored on `ResetPreview`.
+#[derive(Resource, Debug)]
+pub struct EditorBridge {
+    commands: Receiver<EditorCommand>,
+    events: Sender<EditorEvent>,
+    /// The state to restore on `ResetPreview`, once a preview exists.
+    previous: Option<PreviewRestore>,
+}
+
+/// What the preview replaced.
+#[derive(Debug)]
+struct PreviewRestore {
+    container: Option<AspectRatioContainer>,
+    ui_scale: f32,
+}
+
+/// The editor's end of the channel, returned by `EditorBridge::new`.
+#[derive(Clone, Debug)]
+pub struct EditorHandle {
+    /// Sends commands to the game.
+    pub commands: Sender<EditorCommand>,
+    /// Receives events from the game.
+    pub events: Receiver<EditorEvent>,
+}
+
+impl EditorBridge {
+    /// Creates a connected bridge and handle.
+    pub fn new() -> (Self, EditorHandle) {
+        let (command_sender, command_receiver) = crossbeam_channel::unbounded();
+        let (event_sender, event_receiver) = crossbeam_channel::unbounded();
+        (
+            Self {
+                commands: command_receiver,
+                events: event_sender,
+                previous: None,
+            },
+            EditorHandle {
+                commands: command_sender,
+                events: event_receiver,
+            },
+        )
+    }
+}
+
+/// The node the game is previewed in while the editor moves or resizes it.
+#[derive(Component)]
+pub(crate) struct EditorPreview;
+
+pub(crate) fn apply_editor_commands(
+    mut commands: Commands,
+    mut bridge: ResMut<EditorBridge>,
+    container: Option<Res<AspectRatioContainer>>,
+    mut resolution: ResMut<Resolution>,
+    mut policy: ResMut<FitPolicy>,
+    letterbox: Res<Letterbox>,
+    mut previews: Query<(Entity, &mut Node), With<EditorPreview>>,
+) {
+    let bridge = &mut *bridge;
+    let existing = previews.single_mut().ok();
+    // Edits are collected on a copy of the preview node and written back once at the end.
+    let mut preview = existing
+        .as_ref()
+        .map(|(_, node)| (**node).clone())
+        .unwrap_or_else(preview_node);
+    let mut preview_edited = false;
+    let mut preview_removed = false;
+
+    for command in bridge.commands.try_iter() {
+        match command {
+            EditorCommand::SetResolution(value) => *resolution = value,
+            EditorCommand::SetFitPolicy(value) => *policy = value,
+            EditorCommand::MovePreview(position) => {
+                preview.left = Val::Px(position.x);
+                preview.top = Val::Px(position.y);
+                preview_edited = true;
+            }
+            EditorCommand::ResizePreview(size) => {
+                preview.width = Val::Px(size.x);
+                preview.height = Val::Px(size.y);
+                preview_edited = true;
+            }
+            EditorCommand::ResetPreview => {
+                preview = preview_node();
+                preview_edited = false;
+                preview_removed = true;
+            }
+            EditorCommand::SetDebugOverlay(Some(overlay)) => commands.insert_resource(overlay),
+            EditorCommand::SetDebugOverlay(None) => commands.remove_resource::<DebugOverlay>(),
+            EditorCommand::RequestSnapshot => {
+                // Reports the layout as of this frame; edits above apply on the next relayout.
+                let snapshot = LayoutSnapshot::capture(&letterbox, &resolution, *policy);
+                let _ = bridge.events.send(EditorEvent::Snapshot(snapshot));
+            }
+        }
+    }
+
+    let mut current_container = container.as_deref().copied();
+    if preview_removed {
+        if let Some((entity, _)) = &existing {
+            commands.entity(*entity).despawn();
+        }
+        if let Some(previous) = bridge.previous.take() {
+            match previous.container {
+                Some(container) => commands.insert_resource(container),
+                None => commands.remove_resource::<AspectRatioContainer>(),
+            }
+            commands.queue(move |world: &mut World| {
+                set_ui_scale(world, previous.ui_scale);
+            });
+            current_container = previous.container;
+        }
+    }
+    if !preview_edited {
+        return;
+    }
+
+    match existing {
+        Some((_, mut node)) if !preview_removed => *node = preview,
+        _ => {
+            let entity = commands
+                .spawn((EditorPreview, Name::new("Editor Preview"), preview))
+                .id();
+            commands.insert_resource(AspectRatioContainer(entity));
+            commands.queue(move |world: &mut World| {
+                let ui_scale = set_ui_scale(world, 1.0);
+                world.resource_mut::<EditorBridge>().previous = Some(PreviewRestore {
+                    container: current_container,
+                    ui_scale,
+                });
+            });
+        }
+    }
+}
+
+/// Sets the UI scale the plugin drives, returning the previous one.
+fn set_ui_scale(world: &mut World, scale: f32) -> f32 {
+    match world.get_resource_mut::<DesiredUiScale>() {
+        Some(mut desired) => std::mem::replace(&mut desired.0, scale),
+        None => std::mem::replace(&mut world.resource_mut::<UiScale>().0, scale),
+    }
+}
+
+/// The preview node before any edit: covering the whole window.
+fn preview_node() -> Node {
+    Node {
+        position_type: PositionType::Absolute,
+        width: Val::Percent(100.0),
+        height: Val::Percent(100.0),
+        ..default()
+    }
+}
@@ -0,0 +1,223 @@
+use bevy::prelude::*;
+
+use crate::layout::{LayoutSource, ScreenSimulation};
+use crate::{Hud, RESIZE_COVER_Z};
+
+/// Sanity limits on the window's aspect ratio, and what to do past them.
+///
+/// Within `min_aspect..=max_aspect` the layout is unaffected. When the screen gets wider or
+/// taller than that (a 32:9 monitor, a window dragged down to a thin column), `fallback`
+/// kicks in: the active one is published in `ActiveExtremeFallback`, and
+/// `ExtremeAspectChanged` is sent whenever it switches on or off. Under an `AspectPreview` the
+/// simulated screen is checked instead of the window.
+#[derive(Resource, Clone, Debug, PartialEq)]
+pub struct ExtremeAspectRatio {
+    /// The narrowest supported aspect ratio (width divided by height), e.g. `1.0 / 2.0`.
+    pub min_aspect: f32,
+    /// The widest supported aspect ratio, e.g. `3.0` (between 21:9 and 32:9).
+    pub max_aspect: f32,
+    /// What to do outside the supported range.
+    pub fallback: ExtremeAspectFallback,
+    /// The message shown by `ExtremeAspectFallback::ResizeCover`.
+    pub cover_text: String,
+    /// The background of `ExtremeAspectFallback::ResizeCover`.
+    pub cover_color: Color,
+}
+
+impl Default for ExtremeAspectRatio {
+    fn default() -> Self {
+        Self {
+            min_aspect: 1.0 / 2.0,
+            max_aspect: 3.0,
+            fallback: ExtremeAspectFallback::default(),
+            cover_text: "Please resize the window".to_string(),
+            cover_color: Color::BLACK,
+        }
+    }
+}
+
+impl ExtremeAspectRatio {
+    /// Whether a screen of `size` is outside the supported range.
+    pub fn is_extreme(&self, size: Vec2) -> bool {
+        let aspect = size.x / size.y;
+        aspect < self.min_aspect || aspect > self.max_aspect
+    }
+
+    /// The largest size with a supported aspect ratio that fits in `size`.
+    pub fn clamp_size(&self, size: Vec2) -> Vec2 {
+        let aspect = (size.x / size.y).clamp(self.min_aspect, self.max_aspect);
+        if size.x / size.y > aspect {
+            Vec2::new(size.y * aspect, size.y)
+        } else {
+            Vec2::new(size.x, size.x / aspect)
+        }
+    }
+}
+
+/// What the plugin does while the screen is outside the `ExtremeAspectRatio` limits.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ExtremeAspectFallback {
+    /// Lays out as if the screen had the nearest supported aspect ratio, so the bars grow
+    /// instead of the HUD drifting apart.
+    #[default]
+    Clamp,
+    /// Keeps the layout and swaps the HUD: nodes marked `HudVariant::Full` are hidden and
+    /// nodes marked `HudVariant::Simplified` are shown.
+    SimplifiedHud,
+    /// Covers the game with `cover_text`, asking the player to resize the window.
+    ResizeCover,
+}
+
+/// The fallback currently applied by `ExtremeAspectRatio`, or `None` within its limits.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ActiveExtremeFallback(pub Option<ExtremeAspectFallback>);
+
+/// Sent when the screen crosses the `ExtremeAspectRatio` limits, or the fallback changes.
+#[derive(Message, Clone, Copy, Debug, PartialEq)]
+pub struct ExtremeAspectChanged {
+    /// The screen's aspect ratio (width divided by height).
+    pub aspect: f32,
+    /// The fallback now applied, or `None` when back within the limits.
+    pub fallback: Option<ExtremeAspectFallback>,
+}
+
+/// Which HUD layout a node belongs to under `ExtremeAspectFallback::SimplifiedHud`.
+///
+/// The plugin sets the node's `Visibility`: `Full` nodes are visible unless the simplified
+/// layout is active, `Simplified` nodes only while it is.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[require(Visibility)]
+pub enum HudVariant {
+    /// Part of the regular HUD.
+    Full,
+    /// Part of the simplified HUD for extreme aspect ratios.
+    Simplified,
+}
+
+/// The cover spawned by `ExtremeAspectFallback::ResizeCover`.
+#[derive(Component)]
+pub(crate) struct ResizeCover;
+
+/// Tracks whether the screen is past the limits and announces changes.
+pub(crate) fn detect_extreme_aspect(
+    source: LayoutSource,
+    screen: ScreenSimulation,
+    extreme: Option<Res<ExtremeAspectRatio>>,
+    mut active: ResMut<ActiveExtremeFallback>,
+    mut changed: MessageWriter<ExtremeAspectChanged>,
+) {
+    let Some(size) = source.size().map(|size| screen.screen_size(size)) else {
+        return;
+    };
+    let fallback = extreme.and_then(|extreme| extreme.is_extreme(size).then_some(extreme.fallback));
+    if active.0 != fallback {
+        active.0 = fallback;
+        changed.write(ExtremeAspectChanged {
+            aspect: size.x / size.y,
+            fallback,
+        });
+    }
+}
+
+/// Shows the HUD variant matching the active fallback.
+pub(crate) fn apply_hud_variants(
+    active: Res<ActiveExtremeFallback>,
+    mut variants: Query<(Ref<HudVariant>, &mut Visibility)>,
+) {
+    let simplified = active.0 == Some(ExtremeAspectFallback::SimplifiedHud);
+    for (variant, mut visibility) in variants.iter_mut() {
+        if !active.is_changed() && !variant.is_changed() {
+            continue;
+        }
+        let visible = (*variant == HudVariant::Simplified) == simplified;
+        *visibility = if visible {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
+
+/// Spawns or despawns the "please resize" cover.
+///
+/// The cover is a sibling of the HUD, so it follows the HUD's parent or container.
+pub(crate) fn update_resize_cover(
+    mut commands: Commands,
+    active: Res<ActiveExtremeFallback>,
+    extreme: Option<Res<ExtremeAspectRatio>>,
+    hud: Res<Hud>,
+    parents: Query<&ChildOf>,
+    covers: Query<Entity, With<ResizeCover>>,
+) {
+    for cover in covers.iter() {
+        commands.entity(cover).despawn();
+    }
+    let Some(extreme) = extreme else {
+        return;
+    };
+    let Ok(base) = parents.get(hud.0) else {
+        return;
+    };
+    if active.0 != Some(ExtremeAspectFallback::ResizeCover) {
+        return;
+    }
+
+    commands.spawn((
+        Name::new("Resize Cover"),
+        ResizeCover,
+        prompt_cover(
+            extreme.cover_text.clone(),
+            extreme.cover_color,
+            RESIZE_COVER_Z,
+        ),
+        ChildOf(base.parent()),
+    ));
+}
+
+/// A full-size node showing `text` over the game UI, stacked at `z_index`.
+///
+/// Spawn it as a sibling of the HUD. It blocks pointer input to the game.
+pub(crate) fn prompt_cover(text: impl Into<String>, color: Color, z_index: i32) -> impl Bundle {
+    (
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            position_type: PositionType::Absolute,
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        BackgroundColor(color),
+        GlobalZIndex(z_index),
+        children![(
+            Text::new(text),
+            TextLayout::new_with_justify(Justify::Center),
+        )],
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clamp_size_keeps_supported_aspects() {
+        let extreme = ExtremeAspectRatio::default();
+        // 16:9 is within the limits.
+        assert!(!extreme.is_extreme(Vec2::new(1920.0, 1080.0)));
+        assert_eq!(
+            extreme.clamp_size(Vec2::new(1920.0, 1080.0)),
+            Vec2::new(1920.0, 1080.0)
+        );
+        // 32:9 is narrowed to 3:1, a thin column widened to 1:2.
+        assert!(extreme.is_extreme(Vec2::new(3840.0, 1080.0)));
+        assert_eq!(
+            extreme.clamp_size(Vec2::new(3840.0, 1080.0)),
+            Vec2::new(3240.0, 1080.0)
+        );
+        assert_eq!(
+            extreme.clamp_size(Vec2::new(300.0, 1000.0)),
+            Vec2::new(300.0, 600.0)
+        );
+    }
+}
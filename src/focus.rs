@@ -0,0 +1,112 @@
+use bevy::input_focus::{InputFocus, InputFocusVisible};
+use bevy::prelude::*;
+use bevy::ui::{ComputedNode, UiGlobalTransform};
+
+/// Draws an outline around the focused UI element, with its size in virtual pixels.
+///
+/// Insert this resource to highlight the entity in `InputFocus`, so keyboard and gamepad focus
+/// navigation stays visible. The outline keeps the same thickness relative to the HUD at every
+/// window size and is snapped to whole physical pixels, including when the HUD is scaled by
+/// its transform. An `Outline` already on the element is restored when it loses focus.
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct FocusOutline {
+    /// The outline color.
+    pub color: Color,
+    /// The outline thickness, in virtual pixels.
+    pub width: f32,
+    /// The gap between the element and its outline, in virtual pixels.
+    pub offset: f32,
+    /// Show the outline even when `InputFocusVisible` is off (e.g. after a mouse click).
+    pub always_visible: bool,
+}
+
+impl Default for FocusOutline {
+    fn default() -> Self {
+        Self {
+            color: Color::WHITE,
+            width: 2.0,
+            offset: 2.0,
+            always_visible: false,
+        }
+    }
+}
+
+impl FocusOutline {
+    /// Builds the outline for a node laid out at `layout_scale` physical pixels per virtual
+    /// pixel and drawn scaled by `transform_scale` (its and its ancestors' `UiTransform`s).
+    fn outline(&self, layout_scale: f32, transform_scale: f32) -> Outline {
+        // The outline is sized in the node's layout space, before the transform is applied.
+        let physical_scale = layout_scale * transform_scale.max(f32::EPSILON);
+        let snap = |value: f32| (value * physical_scale).round() / physical_scale;
+        Outline::new(
+            Val::Px(snap(self.width).max(physical_scale.recip())),
+            Val::Px(snap(self.offset)),
+            self.color,
+        )
+    }
+}
+
+/// Marks the element currently outlined by `FocusOutline`, keeping its own outline, if any.
+#[derive(Component, Clone, Debug)]
+pub(crate) struct FocusOutlined(Option<Outline>);
+
+/// Moves the outline to the focused element.
+///
+/// Runs after layout so the scale read from `ComputedNode` includes this frame's `UiScale`.
+/// The scale of `UiGlobalTransform` covers HUDs scaled by a transform instead, as in embedded
+/// mode or during a `HudScaleAnimation`.
+pub(crate) fn update_focus_outline(
+    mut commands: Commands,
+    settings: Option<Res<FocusOutline>>,
+    focus: Option<Res<InputFocus>>,
+    focus_visible: Option<Res<InputFocusVisible>>,
+    mut nodes: Query<(
+        &ComputedNode,
+        &UiGlobalTransform,
+        Option<&mut Outline>,
+        Option<&FocusOutlined>,
+    )>,
+    outlined: Query<Entity, With<FocusOutlined>>,
+) {
+    let target = settings.as_ref().and_then(|settings| {
+        let visible = settings.always_visible || focus_visible.is_some_and(|visible| visible.0);
+        focus.as_ref().and_then(|focus| focus.0).filter(|_| visible)
+    });
+
+    for entity in outlined.iter() {
+        if Some(entity) == target {
+            continue;
+        }
+        let Ok((_, _, _, Some(FocusOutlined(previous)))) = nodes.get(entity) else {
+            continue;
+        };
+        let mut entity = commands.entity(entity);
+        entity.remove::<FocusOutlined>();
+        match previous {
+            Some(previous) => entity.insert(*previous),
+            None => entity.remove::<Outline>(),
+        };
+    }
+
+    let (Some(settings), Some(target)) = (settings, target) else {
+        return;
+    };
+    let Ok((computed, transform, current, marker)) = nodes.get_mut(target) else {
+        return;
+    };
+    let (transform_scale, _, _) = transform.to_scale_angle_translation();
+    let outline = settings.outline(
+        computed.inverse_scale_factor.recip(),
+        transform_scale.x.abs(),
+    );
+    match (current, marker) {
+        (Some(mut current), Some(_)) => {
+            current.set_if_neq(outline);
+        }
+        (current, _) => {
+            commands
+                .entity(target)
+                .insert((FocusOutlined(current.as_deref().copied()), outline));
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use bevy::prelude::*;
+
+use crate::{AspectRatioMaskSide, Hud, Letterbox};
+
+/// Resource pointing to four HUD containers aligned to the `Letterbox::gutter` rects.
+///
+/// Spawned when `AspectRatioPlugin::hud_gutters` is set. The containers are children of the
+/// HUD, sized in virtual pixels like the rest of it, and follow the gutters as the window is
+/// resized. They clip their children and don't block pointer input. Use them with
+/// `FitPolicy::Expand` to place decorations in the extra world shown on wide or tall screens;
+/// a gutter that doesn't exist at the current aspect ratio has zero size.
+///
+/// ```rust,no_run
+/// # use bevy::prelude::*;
+/// # use bevy_aspect_ratio_mask::{AspectRatioMaskSide, HudGutters};
+/// fn decorate(mut commands: Commands, gutters: Res<HudGutters>, assets: Res<AssetServer>) {
+///     commands.entity(gutters.get(AspectRatioMaskSide::Left)).with_child((
+///         Node { width: Val::Percent(100.0), height: Val::Percent(100.0), ..default() },
+///         ImageNode::new(assets.load("vines.png")),
+///     ));
+/// }
+/// ```
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct HudGutters {
+    pub left: Entity,
+    pub right: Entity,
+    pub top: Entity,
+    pub bottom: Entity,
+}
+
+impl HudGutters {
+    /// Returns the gutter container for `side`.
+    pub fn get(&self, side: AspectRatioMaskSide) -> Entity {
+        match side {
+            AspectRatioMaskSide::Left => self.left,
+            AspectRatioMaskSide::Right => self.right,
+            AspectRatioMaskSide::Top => self.top,
+            AspectRatioMaskSide::Bottom => self.bottom,
+        }
+    }
+}
+
+/// Marks a gutter container with the side it is aligned to.
+#[derive(Component)]
+pub(crate) struct HudGutter(AspectRatioMaskSide);
+
+pub(crate) fn spawn_hud_gutters(mut commands: Commands, hud: Res<Hud>) {
+    let [left, right, top, bottom] = AspectRatioMaskSide::ALL.map(|side| {
+        commands
+            .spawn((
+                Name::new("Hud Gutter"),
+                HudGutter(side),
+                Node {
+                    position_type: PositionType::Absolute,
+                    overflow: Overflow::clip(),
+                    ..default()
+                },
+                Pickable::IGNORE,
+                ChildOf(hud.0),
+            ))
+            .id()
+    });
+    commands.insert_resource(HudGutters {
+        left,
+        right,
+        top,
+        bottom,
+    });
+}
+
+/// Moves the gutter containers onto the current gutters.
+pub(crate) fn update_hud_gutters(
+    letterbox: Res<Letterbox>,
+    mut gutters: Query<(&HudGutter, &mut Node)>,
+) {
+    // From container pixels to the HUD's virtual pixels.
+    let to_virtual = letterbox.container_to_virtual();
+    for (gutter, mut node) in gutters.iter_mut() {
+        let rect = letterbox.gutter(gutter.0);
+        let min = to_virtual.transform_point2(rect.min);
+        let size = rect.size() / letterbox.scale;
+        node.left = Val::Px(min.x);
+        node.top = Val::Px(min.y);
+        node.width = Val::Px(size.x);
+        node.height = Val::Px(size.y);
+    }
+}
@@ -0,0 +1,116 @@
+use bevy::prelude::*;
+use bevy::ui::OverrideClip;
+
+use crate::{EffectiveDpi, Hud};
+
+/// Inflates the pointer area of interactive HUD nodes when the HUD is drawn small.
+///
+/// While this resource is present, every HUD descendant with an `Interaction` (e.g. a
+/// `Button`) gets an invisible hit area centered on it. As long as a virtual pixel covers at
+/// least `min_scale` physical pixels (see `EffectiveDpi`) the hit area matches the node;
+/// below that it grows so it keeps the physical size it would have at `min_scale`, up to
+/// `max_inflation` times the node's size. Visuals and layout are unchanged, and the hit area
+/// ignores the clipping of its ancestors.
+///
+/// Pointer events on the hit area bubble to the node like events on its children, so picking
+/// observers and `Hovered` see the inflated area. The legacy `Interaction` component is still
+/// computed from the node's own rect.
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct HitTargetInflation {
+    /// Physical pixels per virtual pixel below which hit areas grow.
+    pub min_scale: f32,
+    /// The largest hit area relative to the node's size.
+    pub max_inflation: f32,
+}
+
+impl Default for HitTargetInflation {
+    fn default() -> Self {
+        Self {
+            min_scale: 1.0,
+            max_inflation: 1.5,
+        }
+    }
+}
+
+impl HitTargetInflation {
+    /// The hit area's size relative to the node's at `physical_per_virtual`.
+    pub fn inflation(&self, physical_per_virtual: f32) -> f32 {
+        if physical_per_virtual <= 0.0 {
+            return 1.0;
+        }
+        (self.min_scale / physical_per_virtual).clamp(1.0, self.max_inflation.max(1.0))
+    }
+}
+
+/// The invisible node that catches pointer events for an interactive HUD node.
+#[derive(Component)]
+pub(crate) struct InflatedHitArea;
+
+/// Points from an interactive node to its `InflatedHitArea`.
+#[derive(Component)]
+pub(crate) struct HasInflatedHitArea(Entity);
+
+/// Gives new interactive HUD nodes a hit area and resizes all hit areas to the current scale.
+#[allow(clippy::type_complexity)]
+pub(crate) fn inflate_hit_targets(
+    mut commands: Commands,
+    inflation: Res<HitTargetInflation>,
+    dpi: Res<EffectiveDpi>,
+    hud: Res<Hud>,
+    targets: Query<Entity, (With<Interaction>, Without<HasInflatedHitArea>)>,
+    parents: Query<&ChildOf>,
+    mut areas: Query<&mut Node, With<InflatedHitArea>>,
+) {
+    let factor = inflation.inflation(dpi.physical_per_virtual);
+    let node = hit_area_node(factor);
+
+    for target in targets.iter() {
+        if !parents
+            .iter_ancestors(target)
+            .any(|ancestor| ancestor == hud.0)
+        {
+            continue;
+        }
+        let area = commands
+            .spawn((
+                Name::new("Inflated Hit Area"),
+                InflatedHitArea,
+                node.clone(),
+                ZIndex(-1),
+                OverrideClip,
+                ChildOf(target),
+            ))
+            .id();
+        commands.entity(target).insert(HasInflatedHitArea(area));
+    }
+
+    if inflation.is_changed() || dpi.is_changed() {
+        for mut area in areas.iter_mut() {
+            *area = node.clone();
+        }
+    }
+}
+
+/// Despawns all hit areas once `HitTargetInflation` is removed.
+pub(crate) fn remove_inflated_hit_areas(
+    mut commands: Commands,
+    targets: Query<(Entity, &HasInflatedHitArea)>,
+) {
+    for (target, area) in targets.iter() {
+        commands.entity(area.0).try_despawn();
+        commands.entity(target).remove::<HasInflatedHitArea>();
+    }
+}
+
+/// A node `factor` times the size of its parent, centered on it.
+fn hit_area_node(factor: f32) -> Node {
+    let inset = Val::Percent(-50.0 * (factor - 1.0));
+    Node {
+        position_type: PositionType::Absolute,
+        left: inset,
+        top: inset,
+        width: Val::Percent(100.0 * factor),
+        height: Val::Percent(100.0 * factor),
+        ..default()
+    }
+}
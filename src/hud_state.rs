@@ -0,0 +1,213 @@
+use std::collections::HashMap;
+
+use bevy::ecs::world::EntityRef;
+use bevy::prelude::*;
+
+use crate::{HudLayer, HudScaleAnimation, VirtualTween};
+
+/// Identifies a HUD element across respawns, so its runtime state can be saved and restored
+/// with `HudState`.
+///
+/// Keys must be unique among the elements alive at the same time.
+#[derive(Component, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct HudStateKey(pub String);
+
+impl HudStateKey {
+    /// A key named `key`.
+    pub fn new(key: impl Into<String>) -> Self {
+        Self(key.into())
+    }
+}
+
+/// The runtime state of one keyed HUD element. Components the element didn't have are `None`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HudElementState {
+    /// Whether the element is shown.
+    pub visibility: Option<Visibility>,
+    /// The element's offset, scale and rotation.
+    pub transform: Option<UiTransform>,
+    /// A scale animation, with its progress.
+    pub scale_animation: Option<HudScaleAnimation>,
+    /// A tween, with its progress.
+    pub tween: Option<VirtualTween>,
+    /// The element's layer, if it is one.
+    pub layer: Option<HudLayer>,
+}
+
+impl HudElementState {
+    fn capture(entity: EntityRef) -> Self {
+        Self {
+            visibility: entity.get::<Visibility>().copied(),
+            transform: entity.get::<UiTransform>().copied(),
+            scale_animation: entity.get::<HudScaleAnimation>().copied(),
+            tween: entity.get::<VirtualTween>().copied(),
+            layer: entity.get::<HudLayer>().copied(),
+        }
+    }
+
+    /// Inserts the captured components, and removes the ones the element didn't have.
+    fn apply(&self, entity: &mut EntityCommands) {
+        fn apply_one<T: Component>(entity: &mut EntityCommands, component: Option<T>) {
+            match component {
+                Some(component) => {
+                    entity.insert(component);
+                }
+                None => {
+                    entity.remove::<T>();
+                }
+            }
+        }
+        apply_one(entity, self.visibility);
+        apply_one(entity, self.transform);
+        apply_one(entity, self.scale_animation);
+        apply_one(entity, self.tween);
+        apply_one(entity, self.layer);
+    }
+}
+
+/// A snapshot of the runtime state of every HUD element with a `HudStateKey`: visibility,
+/// `UiTransform`, running `HudScaleAnimation`s and `VirtualTween`s, and `HudLayer` settings.
+///
+/// Capture it before pausing, reloading a scene or hot-reloading, and restore it afterwards,
+/// so elements don't jump back to where they were spawned. Elements are matched by key, so
+/// restoring works on respawned entities: keys that don't exist yet are applied as soon as an
+/// element with that key is spawned, for up to `PENDING_TIMEOUT_FRAMES` frames, or until
+/// `HudState::clear_pending`. Components the element didn't have when captured are removed
+/// on restore, e.g. an animation started after the capture. Layout (`Node`) is left alone,
+/// so changes to how the elements are spawned still take effect.
+///
+/// `HudLayout` elements are keyed by their name and keep their state across hot reloads
+/// automatically.
+///
+/// ```rust,no_run
+/// # use bevy::prelude::*;
+/// # use bevy_aspect_ratio_mask::HudState;
+/// #[derive(Resource)]
+/// struct SavedHud(HudState);
+///
+/// fn before_reload(mut commands: Commands) {
+///     commands.queue(|world: &mut World| {
+///         let state = HudState::capture(world);
+///         world.insert_resource(SavedHud(state));
+///     });
+/// }
+///
+/// fn after_reload(mut commands: Commands) {
+///     commands.queue(|world: &mut World| {
+///         if let Some(SavedHud(state)) = world.remove_resource::<SavedHud>() {
+///             state.restore(world);
+///         }
+///     });
+/// }
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HudState {
+    elements: HashMap<String, HudElementState>,
+}
+
+impl HudState {
+    /// How many frames restored state waits for elements that haven't been spawned yet.
+    pub const PENDING_TIMEOUT_FRAMES: u32 = 300;
+
+    /// Captures the state of every keyed element.
+    pub fn capture(world: &mut World) -> Self {
+        let mut elements = world.query::<(EntityRef, &HudStateKey)>();
+        Self {
+            elements: elements
+                .iter(world)
+                .map(|(entity, key)| (key.0.clone(), HudElementState::capture(entity)))
+                .collect(),
+        }
+    }
+
+    /// Captures the state of the keyed descendants of `root`.
+    #[cfg(feature = "layout_asset")]
+    pub(crate) fn capture_descendants(world: &mut World, root: Entity) -> Self {
+        let mut elements = world.query::<(EntityRef, &HudStateKey)>();
+        let world = &*world;
+        let mut state = Self::default();
+        for (entity, key) in elements.iter(world) {
+            let mut ancestor = entity.get::<ChildOf>().map(ChildOf::parent);
+            while let Some(parent) = ancestor {
+                if parent == root {
+                    state.insert(key.0.clone(), HudElementState::capture(entity));
+                    break;
+                }
+                ancestor = world.get::<ChildOf>(parent).map(ChildOf::parent);
+            }
+        }
+        state
+    }
+
+    /// Applies the state to the keyed elements that exist, and to the others once they are
+    /// spawned. Pending state from an earlier restore is replaced key by key.
+    pub fn restore(self, world: &mut World) {
+        self.restore_later(world);
+        world.run_system_cached(restore_pending_hud_state).ok();
+    }
+
+    /// Queues the state for the keyed elements spawned from now on.
+    pub(crate) fn restore_later(self, world: &mut World) {
+        let mut pending = world.get_resource_or_init::<PendingHudState>();
+        pending.state.elements.extend(self.elements);
+        pending.waited = 0;
+    }
+
+    /// Drops the restored state still waiting for its elements to be spawned.
+    pub fn clear_pending(world: &mut World) {
+        world.remove_resource::<PendingHudState>();
+    }
+
+    /// The state of the element with `key`.
+    pub fn get(&self, key: &str) -> Option<&HudElementState> {
+        self.elements.get(key)
+    }
+
+    /// Sets the state of the element with `key`.
+    pub fn insert(&mut self, key: impl Into<String>, state: HudElementState) {
+        self.elements.insert(key.into(), state);
+    }
+
+    /// Iterates over the elements' keys and states.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &HudElementState)> {
+        self.elements
+            .iter()
+            .map(|(key, state)| (key.as_str(), state))
+    }
+
+    /// The number of elements.
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Whether no element was captured.
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+}
+
+/// State restored for elements that haven't been spawned yet.
+#[derive(Resource, Default)]
+pub(crate) struct PendingHudState {
+    state: HudState,
+    /// Frames since the last restore.
+    waited: u32,
+}
+
+/// Applies pending state to keyed elements, removing each entry once applied, and drops the
+/// rest after `HudState::PENDING_TIMEOUT_FRAMES` frames.
+pub(crate) fn restore_pending_hud_state(
+    mut commands: Commands,
+    mut pending: ResMut<PendingHudState>,
+    elements: Query<(Entity, &HudStateKey)>,
+) {
+    for (entity, key) in elements.iter() {
+        if let Some(state) = pending.state.elements.remove(&key.0) {
+            state.apply(&mut commands.entity(entity));
+        }
+    }
+    pending.waited += 1;
+    if pending.state.is_empty() || pending.waited > HudState::PENDING_TIMEOUT_FRAMES {
+        commands.remove_resource::<PendingHudState>();
+    }
+}
@@ -0,0 +1,355 @@
+use bevy::input_focus::tab_navigation::{NavAction, TabGroup, TabNavigation};
+use bevy::input_focus::InputFocus;
+use bevy::prelude::*;
+
+use crate::{AspectRatioMaskSide, Letterbox, MaskRegions};
+
+/// A layer of the HUD with its own input routing rules.
+///
+/// Spawn layers as direct children of the HUD. The plugin stacks them by `kind`, then by
+/// `order` (modals above overlays above the gameplay HUD), and enforces how each layer treats
+/// pointer and keyboard input, so menus don't need ad-hoc `Pickable` and focus fiddling.
+///
+/// ```rust,no_run
+/// # use bevy::prelude::*;
+/// # use bevy_aspect_ratio_mask::{Hud, HudLayer};
+/// fn open_pause_menu(mut commands: Commands, hud: Res<Hud>) {
+///     commands.entity(hud.0).with_child((
+///         HudLayer::modal(),
+///         Node {
+///             width: Val::Percent(100.0),
+///             height: Val::Percent(100.0),
+///             ..default()
+///         },
+///         children![Button],
+///     ));
+/// }
+/// ```
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+#[require(Node)]
+pub struct HudLayer {
+    /// Which group of layers this one is stacked in.
+    pub kind: HudLayerKind,
+    /// The stacking order within layers of the same kind; higher is on top.
+    ///
+    /// Orders are clamped to `HudLayer::MIN_ORDER..=HudLayer::MAX_ORDER`, so a layer always
+    /// stays below every layer of a higher kind.
+    pub order: i32,
+    /// How pointer input (mouse and touch) reaches what's below the layer.
+    pub pointer: InputRouting,
+    /// How keyboard focus navigation treats the layer.
+    pub keyboard: InputRouting,
+}
+
+impl HudLayer {
+    /// The lowest effective `order`; lower orders stack like this one.
+    pub const MIN_ORDER: i32 = -(1 << 19);
+    /// The highest effective `order`; higher orders stack like this one.
+    pub const MAX_ORDER: i32 = (1 << 19) - 1;
+
+    /// The gameplay HUD: lets pointer input through to the world and doesn't trap focus.
+    pub fn gameplay() -> Self {
+        Self {
+            kind: HudLayerKind::Gameplay,
+            order: 0,
+            pointer: InputRouting::Pass,
+            keyboard: InputRouting::Pass,
+        }
+    }
+
+    /// An overlay, such as a notification or chat panel: lets pointer input through around
+    /// its elements and is navigated as its own tab group.
+    pub fn overlay() -> Self {
+        Self {
+            kind: HudLayerKind::Overlay,
+            keyboard: InputRouting::Block,
+            ..Self::gameplay()
+        }
+    }
+
+    /// A modal, such as a pause menu: takes all pointer and keyboard input.
+    pub fn modal() -> Self {
+        Self {
+            kind: HudLayerKind::Modal,
+            order: 0,
+            pointer: InputRouting::Capture,
+            keyboard: InputRouting::Capture,
+        }
+    }
+
+    /// Stacks the layer at `order` within its kind.
+    pub fn with_order(mut self, order: i32) -> Self {
+        self.order = order;
+        self
+    }
+
+    /// The `ZIndex` of the layer among the HUD's children. The one below is for its shield.
+    fn z_index(&self) -> i32 {
+        let base: i32 = match self.kind {
+            HudLayerKind::Gameplay => 0,
+            HudLayerKind::Overlay => 1 << 20,
+            HudLayerKind::Modal => 2 << 20,
+        };
+        let order = self.order.clamp(Self::MIN_ORDER, Self::MAX_ORDER);
+        base.saturating_add(order)
+            .saturating_mul(2)
+            .saturating_add(1)
+    }
+}
+
+/// The groups `HudLayer`s are stacked in, from bottom to top.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HudLayerKind {
+    /// The always-on gameplay HUD.
+    Gameplay,
+    /// Panels drawn over the gameplay HUD.
+    Overlay,
+    /// Dialogs and menus that take over input.
+    Modal,
+}
+
+/// How a `HudLayer` routes one kind of input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum InputRouting {
+    /// Pointer input only stops at the layer's elements, and its empty space lets input
+    /// through. For the keyboard, the layer's elements simply join the surrounding tab order.
+    Pass,
+    /// Pointer input stops anywhere on the layer's node. For the keyboard, the layer is its
+    /// own tab group, visited in stacking order.
+    Block,
+    /// The layer takes all input while visible. Pointer input stops anywhere in the window,
+    /// bars included, and keyboard focus is kept inside the layer's tab group.
+    Capture,
+}
+
+/// The full-window node that stops pointer input under a capturing layer.
+#[derive(Component)]
+pub(crate) struct HudLayerShield(Entity);
+
+/// A node over one of the bars that stops pointer input under a capturing layer.
+///
+/// The `HudLayerShield` is drawn with the HUD, so bars drawn above the HUD, and whatever is
+/// attached to them, would still be reachable without it.
+#[derive(Component)]
+pub(crate) struct HudLayerBarShield {
+    layer: Entity,
+    side: AspectRatioMaskSide,
+}
+
+/// Applies stacking, picking and tab-group settings when a layer is added or changed.
+pub(crate) fn apply_hud_layers(
+    mut commands: Commands,
+    layers: Query<(Entity, &HudLayer, &ChildOf), Changed<HudLayer>>,
+) {
+    for (entity, layer, child_of) in layers.iter() {
+        let mut entity_commands = commands.entity(entity);
+        entity_commands.insert(ZIndex(layer.z_index()));
+        entity_commands.insert(match layer.pointer {
+            InputRouting::Pass => Pickable {
+                should_block_lower: false,
+                is_hoverable: false,
+            },
+            InputRouting::Block | InputRouting::Capture => Pickable::default(),
+        });
+        match layer.keyboard {
+            InputRouting::Pass => {
+                entity_commands.remove::<TabGroup>();
+            }
+            InputRouting::Block => {
+                entity_commands.insert(TabGroup::new(layer.z_index()));
+            }
+            InputRouting::Capture => {
+                entity_commands.insert(TabGroup {
+                    order: layer.z_index(),
+                    modal: true,
+                });
+            }
+        }
+        if layer.pointer == InputRouting::Capture {
+            commands.spawn((
+                HudLayerShield(entity),
+                Name::new("Hud Layer Shield"),
+                Node {
+                    position_type: PositionType::Absolute,
+                    ..default()
+                },
+                ZIndex(layer.z_index() - 1),
+                Pickable::default(),
+                ChildOf(child_of.parent()),
+            ));
+        }
+    }
+}
+
+/// Keeps each shield covering the window while its layer captures pointer input and is shown.
+pub(crate) fn update_hud_layer_shields(
+    mut commands: Commands,
+    letterbox: Res<Letterbox>,
+    layers: Query<(Ref<HudLayer>, &Node, &InheritedVisibility), Without<HudLayerShield>>,
+    mut shields: Query<(Entity, Ref<HudLayerShield>, &mut Node, &mut ZIndex)>,
+) {
+    for (entity, shield, mut node, mut z_index) in shields.iter_mut() {
+        let Ok((layer, layer_node, visibility)) = layers.get(shield.0) else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+        // A changed layer has just spawned a fresh shield if it still captures.
+        if layer.pointer != InputRouting::Capture || (layer.is_changed() && !shield.is_added()) {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        let shown = visibility.get() && layer_node.display != Display::None;
+        let display = if shown { Display::Flex } else { Display::None };
+        let position = -letterbox.bars;
+        let size = letterbox.viewport;
+        if node.display != display
+            || node.left != Val::Px(position.x)
+            || node.top != Val::Px(position.y)
+            || node.width != Val::Px(size.x)
+            || node.height != Val::Px(size.y)
+        {
+            node.display = display;
+            node.left = Val::Px(position.x);
+            node.top = Val::Px(position.y);
+            node.width = Val::Px(size.x);
+            node.height = Val::Px(size.y);
+        }
+        z_index.set_if_neq(ZIndex(layer.z_index() - 1));
+    }
+}
+
+/// Keeps a shield over each bar while a layer captures pointer input and is shown.
+#[allow(clippy::type_complexity)]
+pub(crate) fn update_hud_layer_bar_shields(
+    mut commands: Commands,
+    regions: Option<Res<MaskRegions>>,
+    layers: Query<(Entity, &HudLayer, &Node, &InheritedVisibility), Without<HudLayerBarShield>>,
+    bars: Query<&Node, (With<AspectRatioMaskSide>, Without<HudLayerBarShield>)>,
+    mut shields: Query<(Entity, &HudLayerBarShield, &ChildOf, &mut Node), Without<HudLayer>>,
+) {
+    let shown = |layer: Entity| {
+        layers
+            .get(layer)
+            .ok()
+            .filter(|(_, layer, ..)| layer.pointer == InputRouting::Capture)
+            .map(|(_, _, node, visibility)| visibility.get() && node.display != Display::None)
+    };
+    let bar = |side: AspectRatioMaskSide| {
+        regions
+            .as_deref()
+            .and_then(|regions| bars.get(regions.get(side)).ok())
+    };
+
+    let mut shielded = Vec::new();
+    for (entity, shield, child_of, mut node) in shields.iter_mut() {
+        let current = regions
+            .as_deref()
+            .is_some_and(|regions| regions.root == child_of.parent());
+        let bar = bar(shield.side).filter(|_| current);
+        let (Some(shown), Some(bar)) = (shown(shield.layer), bar) else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+        shielded.push(shield.layer);
+        node.set_if_neq(shield_node(bar, shown));
+    }
+
+    let Some(root) = regions.as_deref().map(|regions| regions.root) else {
+        return;
+    };
+    for (layer, ..) in layers
+        .iter()
+        .filter(|(entity, ..)| !shielded.contains(entity))
+    {
+        let Some(shown) = shown(layer) else {
+            continue;
+        };
+        for side in AspectRatioMaskSide::ALL {
+            let Some(bar) = bar(side) else {
+                continue;
+            };
+            // Above the bar and everything attached to it, other than global z-indices.
+            commands.spawn((
+                HudLayerBarShield { layer, side },
+                Name::new("Hud Layer Bar Shield"),
+                shield_node(bar, shown),
+                ZIndex(1),
+                Pickable::default(),
+                ChildOf(root),
+            ));
+        }
+    }
+}
+
+/// A node covering `bar`, or hidden unless `shown`.
+fn shield_node(bar: &Node, shown: bool) -> Node {
+    Node {
+        display: if shown { bar.display } else { Display::None },
+        position_type: PositionType::Absolute,
+        left: bar.left,
+        top: bar.top,
+        width: bar.width,
+        height: bar.height,
+        ..default()
+    }
+}
+
+/// Keeps keyboard focus inside the tab group of the topmost visible layer that captures it.
+///
+/// Focus outside the layer moves to the first focusable element in it, or is cleared if
+/// there is none.
+pub(crate) fn enforce_keyboard_capture(
+    focus: Option<ResMut<InputFocus>>,
+    layers: Query<(Entity, &HudLayer, &Node, &InheritedVisibility)>,
+    parents: Query<&ChildOf>,
+    navigation: TabNavigation,
+) {
+    let Some(mut focus) = focus else {
+        return;
+    };
+    let Some(focused) = focus.0 else {
+        return;
+    };
+    let capturing = layers
+        .iter()
+        .filter(|(_, layer, node, visibility)| {
+            layer.keyboard == InputRouting::Capture
+                && visibility.get()
+                && node.display != Display::None
+        })
+        .max_by_key(|(_, layer, ..)| layer.z_index());
+    let Some((capturing, ..)) = capturing else {
+        return;
+    };
+    let inside = focused == capturing || parents.iter_ancestors(focused).any(|e| e == capturing);
+    if !inside {
+        match navigation.initialize(capturing, NavAction::First) {
+            Ok(first) => focus.set(first),
+            Err(_) => focus.clear(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn orders_stay_within_their_kind() {
+        let top_overlay = HudLayer::overlay().with_order(i32::MAX);
+        let bottom_modal = HudLayer::modal().with_order(i32::MIN);
+        assert!(top_overlay.z_index() < bottom_modal.z_index());
+        assert!(
+            HudLayer::gameplay().with_order(i32::MAX).z_index()
+                < HudLayer::overlay().with_order(i32::MIN).z_index()
+        );
+        assert_eq!(
+            top_overlay.z_index(),
+            HudLayer::overlay()
+                .with_order(HudLayer::MAX_ORDER)
+                .z_index()
+        );
+        // The shield below the lowest layer still fits.
+        assert!(HudLayer::gameplay().with_order(i32::MIN).z_index() > i32::MIN);
+    }
+}
@@ -0,0 +1,563 @@
+use bevy::ecs::system::SystemParam;
+use bevy::math::Affine2;
+use bevy::prelude::*;
+use bevy::ui::ComputedNode;
+
+use crate::{
+    AspectPreview, AspectRatioMaskSide, ExtremeAspectFallback, ExtremeAspectRatio, Resolution,
+    ResolutionMode,
+};
+
+/// The letterbox layout for a window, expressed in virtual pixels.
+///
+/// The UI is scaled by `scale`, which makes the window `viewport` virtual pixels
+/// wide and tall. The virtual area sits in the middle, with `bars` virtual pixels
+/// of masked space on each side.
+///
+/// The plugin keeps the current layout in this resource. All values are zero until
+/// the first layout has been applied.
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Default)]
+pub struct Letterbox {
+    /// Container pixels per virtual pixel: window logical pixels, or the container
+    /// node's UI pixels in embedded mode.
+    pub scale: f32,
+    /// The container size in virtual pixels.
+    pub viewport: Vec2,
+    /// The thickness of each bar in virtual pixels (left/right in `x`, top/bottom in `y`).
+    pub bars: Vec2,
+    /// The rect the world's virtual resolution is drawn into, in container pixels.
+    ///
+    /// This is where a camera following the active `FitPolicy` shows the world. It matches
+    /// the virtual area under letterboxing, and covers (and extends past) the whole container
+    /// under `FitPolicy::Cover`.
+    pub world: Rect,
+}
+
+impl Letterbox {
+    /// The largest scale at which `resolution` fits inside a container of `window_size` pixels.
+    pub fn fit_scale(window_size: Vec2, resolution: &Resolution) -> f32 {
+        (window_size.x / resolution.width).min(window_size.y / resolution.height)
+    }
+
+    /// Lays out `resolution` at `scale` inside a container of `window_size` pixels.
+    ///
+    /// `scale` is expected to be at most `fit_scale`, so bars never have negative thickness.
+    pub fn new(window_size: Vec2, resolution: &Resolution, scale: f32) -> Self {
+        let viewport = window_size / scale;
+        let bars = ((viewport - resolution.size()) / 2.0).max(Vec2::ZERO);
+        let world = Rect::from_center_size(
+            window_size / 2.0,
+            resolution.size() * Self::fit_scale(window_size, resolution),
+        );
+        Self {
+            scale,
+            viewport,
+            bars,
+            world,
+        }
+    }
+
+    /// Sets the `world` rect for `policy`.
+    pub fn with_policy(mut self, policy: FitPolicy, resolution: &Resolution) -> Self {
+        let container = self.container_size();
+        let world_scale = match policy {
+            FitPolicy::Letterbox | FitPolicy::Expand => Self::fit_scale(container, resolution),
+            FitPolicy::Cover => {
+                (container.x / resolution.width).max(container.y / resolution.height)
+            }
+        };
+        self.world = Rect::from_center_size(container / 2.0, resolution.size() * world_scale);
+        self
+    }
+
+    /// Places this layout, computed for a smaller container, in the middle of a container of
+    /// `container_size` pixels, masking the extra space with the bars.
+    pub fn centered_in(mut self, container_size: Vec2) -> Self {
+        let offset = (container_size - self.container_size()) / 2.0;
+        self.viewport = container_size / self.scale;
+        self.bars += offset / self.scale;
+        self.world = Rect::from_corners(self.world.min + offset, self.world.max + offset);
+        self
+    }
+
+    /// The size of the container (window or container node) in container pixels.
+    pub fn container_size(&self) -> Vec2 {
+        self.viewport * self.scale
+    }
+
+    /// The rect covered by the virtual area (the HUD), in container pixels.
+    ///
+    /// Under `FitPolicy::Cover` this is the guaranteed-safe rect for HUD content.
+    pub fn virtual_rect(&self) -> Rect {
+        Rect::from_corners(
+            self.bars * self.scale,
+            (self.viewport - self.bars) * self.scale,
+        )
+    }
+
+    /// The part of the container on `side` of the `world` rect, in container pixels.
+    ///
+    /// Under `FitPolicy::Expand` this is the extra world shown past the virtual resolution on
+    /// wide or tall screens, where non-critical decorations can go. Left and right gutters span
+    /// the container's full height, top and bottom gutters its full width. A gutter is empty
+    /// (zero-sized) when the world reaches that edge, e.g. under `FitPolicy::Cover`.
+    pub fn gutter(&self, side: AspectRatioMaskSide) -> Rect {
+        let container = self.container_size();
+        let world = self
+            .world
+            .intersect(Rect::from_corners(Vec2::ZERO, container));
+        let (min, max) = match side {
+            AspectRatioMaskSide::Left => (Vec2::ZERO, Vec2::new(world.min.x, container.y)),
+            AspectRatioMaskSide::Right => (Vec2::new(world.max.x, 0.0), container),
+            AspectRatioMaskSide::Top => (Vec2::ZERO, Vec2::new(container.x, world.min.y)),
+            AspectRatioMaskSide::Bottom => (Vec2::new(0.0, world.max.y), container),
+        };
+        Rect {
+            min,
+            max: max.max(min),
+        }
+    }
+
+    /// The gutter on `side` in world units, relative to the camera's position (y up).
+    ///
+    /// Assumes a 2D camera rendering to the whole container and showing the world as the
+    /// active `FitPolicy` expects, e.g. one with `AspectRatioCamera`. Add the camera's
+    /// translation to get world positions.
+    pub fn world_gutter(&self, side: AspectRatioMaskSide, resolution: &Resolution) -> Rect {
+        let gutter = self.gutter(side);
+        let units_per_pixel = resolution.width / self.world.width();
+        let to_world = |point: Vec2| {
+            let offset = (point - self.world.center()) * units_per_pixel;
+            Vec2::new(offset.x, -offset.y)
+        };
+        Rect::from_corners(to_world(gutter.min), to_world(gutter.max))
+    }
+
+    /// The bars as CSS-like insets, in virtual pixels.
+    ///
+    /// Use it as the `padding` or `margin` of your own full-window UI root to inset its
+    /// contents exactly to the virtual area, e.g. for a custom background. In window mode one
+    /// UI pixel is one virtual pixel, so the values can be used as is.
+    pub fn insets(&self) -> UiRect {
+        UiRect::axes(Val::Px(self.bars.x), Val::Px(self.bars.y))
+    }
+
+    /// Extends the virtual area into the bars by up to `max_distortion` times its size
+    /// on each axis, shrinking the bars accordingly.
+    ///
+    /// The scale is unchanged: the HUD gets more layout room rather than a visual stretch.
+    pub fn compensated(mut self, max_distortion: f32) -> Self {
+        let size = self.virtual_size();
+        let stretched = (size * max_distortion.max(1.0)).min(self.viewport.max(size));
+        self.bars = (self.viewport - stretched) / 2.0;
+        self
+    }
+
+    /// The size of the virtual area in virtual pixels.
+    ///
+    /// This is the resolution, unless `AxisCompensation` stretched it.
+    pub fn virtual_size(&self) -> Vec2 {
+        self.viewport - 2.0 * self.bars
+    }
+
+    /// The affine map from virtual pixels (origin at the top-left of the virtual area)
+    /// to container pixels (origin at the top-left of the window or container node).
+    ///
+    /// This and the other `virtual_*` matrices below describe exactly the fitting the plugin
+    /// applies to the HUD, so custom render passes and compute shaders can transform
+    /// coordinates identically. The world follows the active `FitPolicy` instead, which only
+    /// matches the HUD under letterboxing; see `world_to_container` and `world_uv_rect`.
+    /// Use `Mat3::from` to upload them as a `mat3x3` uniform.
+    pub fn virtual_to_container(&self) -> Affine2 {
+        Affine2::from_scale(Vec2::splat(self.scale)) * Affine2::from_translation(self.bars)
+    }
+
+    /// The affine map from container pixels to virtual pixels.
+    pub fn container_to_virtual(&self) -> Affine2 {
+        self.virtual_to_container().inverse()
+    }
+
+    /// The affine map from virtual pixels to normalized container coordinates
+    /// (`0..1` on both axes, y down), as used for sampling a full-window texture.
+    pub fn virtual_to_uv(&self) -> Affine2 {
+        Affine2::from_scale(self.viewport.recip()) * Affine2::from_translation(self.bars)
+    }
+
+    /// The affine map from virtual pixels to normalized device coordinates
+    /// (`-1..1` on both axes, y up) of the container.
+    pub fn virtual_to_ndc(&self) -> Affine2 {
+        Affine2::from_cols(
+            Vec2::new(2.0, 0.0),
+            Vec2::new(0.0, -2.0),
+            Vec2::new(-1.0, 1.0),
+        ) * self.virtual_to_uv()
+    }
+
+    /// The region of the container covered by the virtual area, in normalized container
+    /// coordinates. This is the crop rect to apply when sampling the full-window image.
+    pub fn virtual_uv_rect(&self) -> Rect {
+        Rect::from_corners(
+            self.bars / self.viewport,
+            (self.viewport - self.bars) / self.viewport,
+        )
+    }
+
+    /// The affine map from the world's virtual pixels (origin at the top-left of the
+    /// resolution the world is designed for, y down) to container pixels, under the active
+    /// `FitPolicy`.
+    ///
+    /// This is the mapping of the `world` rect: it matches `virtual_to_container` under
+    /// letterboxing, scales past the container under `FitPolicy::Cover`, and leaves gutters
+    /// under `FitPolicy::Expand`.
+    pub fn world_to_container(&self, resolution: &Resolution) -> Affine2 {
+        Affine2::from_translation(self.world.min)
+            * Affine2::from_scale(self.world.size() / resolution.size())
+    }
+
+    /// The affine map from container pixels to the world's virtual pixels.
+    pub fn container_to_world(&self, resolution: &Resolution) -> Affine2 {
+        self.world_to_container(resolution).inverse()
+    }
+
+    /// The part of the world's resolution that is visible in the container, in normalized
+    /// coordinates of the `world` rect. This is the crop rect to apply when sampling an image
+    /// of the world rendered at its resolution: the whole image, except under
+    /// `FitPolicy::Cover` where the edges past the container are cut off.
+    pub fn world_uv_rect(&self) -> Rect {
+        let visible = self
+            .world
+            .intersect(Rect::from_corners(Vec2::ZERO, self.container_size()));
+        let size = self.world.size().max(Vec2::splat(f32::EPSILON));
+        Rect::from_corners(
+            (visible.min - self.world.min) / size,
+            (visible.max - self.world.min) / size,
+        )
+    }
+}
+
+/// Sent whenever the plugin applies a new `Letterbox` layout.
+///
+/// Carries both the previous and the new layout so animation systems can tween
+/// anchored elements from the old layout to the new one.
+#[derive(Message, Clone, Copy, Debug, PartialEq)]
+pub struct LetterboxChanged {
+    /// The layout before the change.
+    pub previous: Letterbox,
+    /// The layout after the change.
+    pub current: Letterbox,
+}
+
+impl LetterboxChanged {
+    /// How much the UI scale changed.
+    pub fn scale_delta(&self) -> f32 {
+        self.current.scale - self.previous.scale
+    }
+
+    /// How much the window size changed, in virtual pixels.
+    pub fn viewport_delta(&self) -> Vec2 {
+        self.current.viewport - self.previous.viewport
+    }
+
+    /// How much the bar thickness changed, in virtual pixels.
+    pub fn bars_delta(&self) -> Vec2 {
+        self.current.bars - self.previous.bars
+    }
+}
+
+/// How the world and the HUD are fitted into the window.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum FitPolicy {
+    /// The whole virtual area is visible and the leftover space is masked with bars.
+    #[default]
+    Letterbox,
+    /// For competitive games: the world covers the whole window with no bars, cropping
+    /// whatever doesn't fit, while the HUD stays letter-fit inside a centered safe rect so
+    /// every player sees all of it. Compare `Letterbox::world` and `Letterbox::virtual_rect`.
+    ///
+    /// The world camera needs `ScalingMode::AutoMax` for this; add `AspectRatioCamera` to
+    /// your camera to have the plugin set it.
+    Cover,
+    /// No bars: the whole virtual resolution stays visible and the leftover space shows more of
+    /// the world instead of being masked. The HUD stays in the virtual area; the extra space is
+    /// exposed as `Letterbox::gutter` rects, and `AspectRatioPlugin::hud_gutters` spawns HUD
+    /// containers aligned to them for non-critical decorations.
+    ///
+    /// The world camera needs `ScalingMode::AutoMin`, as for letterboxing.
+    Expand,
+}
+
+/// Letterboxes the game view inside an existing UI node instead of the whole window.
+///
+/// In this embedded mode the mask and HUD roots are parented under the container (taking
+/// precedence over `AspectRatioParent`), the virtual area is fitted into the container's
+/// computed size, and the HUD is scaled with its own `UiTransform` so the global `UiScale`
+/// and the rest of your UI are left untouched. Useful for editor-like layouts where the game
+/// is one panel among others.
+///
+/// The layout follows the container's computed size, so resizable or animated panels are
+/// relaid out as they change, not just when the window is resized.
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AspectRatioContainer(pub Entity);
+
+/// Reads the size of the area the virtual resolution is fitted into.
+#[derive(SystemParam)]
+pub(crate) struct LayoutSource<'w, 's> {
+    windows: Query<'w, 's, &'static Window>,
+    container: Option<Res<'w, AspectRatioContainer>>,
+    nodes: Query<'w, 's, &'static ComputedNode>,
+    #[cfg(feature = "testing")]
+    synthetic: Option<Res<'w, crate::SyntheticWindowSize>>,
+}
+
+impl LayoutSource<'_, '_> {
+    /// The window's logical size, or the container node's size in UI pixels in embedded mode.
+    ///
+    /// Returns `None` until the container has a non-empty size.
+    pub fn size(&self) -> Option<Vec2> {
+        let size = match &self.container {
+            Some(container) => {
+                let node = self.nodes.get(container.0).ok()?;
+                node.size() * node.inverse_scale_factor()
+            }
+            None => self.window()?.0,
+        };
+        (size.min_element() > 0.0).then_some(size)
+    }
+
+    /// Physical pixels per container pixel.
+    pub fn physical_scale(&self) -> Option<f32> {
+        match &self.container {
+            Some(container) => Some(
+                self.nodes
+                    .get(container.0)
+                    .ok()?
+                    .inverse_scale_factor()
+                    .recip(),
+            ),
+            None => Some(self.window()?.1),
+        }
+    }
+
+    /// Whether the layout follows the primary window, rather than a container node or a
+    /// `SyntheticWindowSize`.
+    pub fn follows_window(&self) -> bool {
+        #[cfg(feature = "testing")]
+        if self.synthetic.is_some() {
+            return false;
+        }
+        self.container.is_none()
+    }
+
+    /// The window's logical size and scale factor.
+    fn window(&self) -> Option<(Vec2, f32)> {
+        #[cfg(feature = "testing")]
+        if let Some(synthetic) = &self.synthetic {
+            return Some((synthetic.size, synthetic.scale_factor));
+        }
+        let window = self.windows.single().ok()?;
+        Some((window.resolution.size(), window.scale_factor()))
+    }
+}
+
+/// Reads the settings that make the layout use a screen other than the container.
+#[derive(SystemParam)]
+pub(crate) struct ScreenSimulation<'w> {
+    preview: Option<Res<'w, AspectPreview>>,
+    extreme: Option<Res<'w, ExtremeAspectRatio>>,
+}
+
+impl ScreenSimulation<'_> {
+    /// The size of the screen the player sees: the container, or the simulated screen of an
+    /// `AspectPreview`.
+    pub fn screen_size(&self, container_size: Vec2) -> Vec2 {
+        self.preview.as_ref().map_or(container_size, |preview| {
+            preview.simulated_size(container_size)
+        })
+    }
+
+    /// The size the layout is computed for: the screen, clamped to the supported aspect
+    /// ratios under `ExtremeAspectFallback::Clamp`.
+    pub fn layout_size(&self, container_size: Vec2) -> Vec2 {
+        let screen_size = self.screen_size(container_size);
+        match &self.extreme {
+            Some(extreme) if extreme.fallback == ExtremeAspectFallback::Clamp => {
+                extreme.clamp_size(screen_size)
+            }
+            _ => screen_size,
+        }
+    }
+}
+
+/// Makes `Resolution` match the screen under `ResolutionMode::Free`.
+pub(crate) fn follow_free_resolution(
+    source: LayoutSource,
+    screen: ScreenSimulation,
+    mode: Res<ResolutionMode>,
+    mut resolution: ResMut<Resolution>,
+) {
+    if *mode != ResolutionMode::Free {
+        return;
+    }
+    let Some(size) = source.size() else {
+        return;
+    };
+    let size = screen.layout_size(size);
+    resolution.set_if_neq(Resolution {
+        width: size.x,
+        height: size.y,
+    });
+}
+
+/// Run condition: the `AspectRatioContainer` was set or its `ComputedNode` changed.
+///
+/// This catches container resizes that don't come from the window, such as a
+/// resizable editor panel or a container whose size is being animated.
+pub(crate) fn container_resized(
+    container: Option<Res<AspectRatioContainer>>,
+    nodes: Query<Ref<ComputedNode>>,
+) -> bool {
+    let Some(container) = container else {
+        return false;
+    };
+    container.is_changed() || nodes.get(container.0).is_ok_and(|node| node.is_changed())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RESOLUTION: Resolution = Resolution {
+        width: 960.0,
+        height: 540.0,
+    };
+
+    fn layout(window_size: Vec2, policy: FitPolicy) -> Letterbox {
+        let scale = Letterbox::fit_scale(window_size, &RESOLUTION);
+        Letterbox::new(window_size, &RESOLUTION, scale).with_policy(policy, &RESOLUTION)
+    }
+
+    #[test]
+    fn world_matrices_follow_the_fit_policy() {
+        // A 4:3 window, 1440×1080: the resolution fits at 1.5 with 135 pixel bars on top and
+        // bottom, and covers it at 2 with 240 pixels cropped on the left and right.
+        let window = Vec2::new(1440.0, 1080.0);
+
+        let letterbox = layout(window, FitPolicy::Letterbox);
+        let world = letterbox.world_to_container(&RESOLUTION);
+        assert_eq!(world, letterbox.virtual_to_container());
+        assert_eq!(world.transform_point2(Vec2::ZERO), Vec2::new(0.0, 135.0));
+        assert_eq!(letterbox.world_uv_rect(), Rect::new(0.0, 0.0, 1.0, 1.0));
+
+        let cover = layout(window, FitPolicy::Cover);
+        let world = cover.world_to_container(&RESOLUTION);
+        assert_eq!(world.transform_point2(Vec2::ZERO), Vec2::new(-240.0, 0.0));
+        assert_eq!(
+            world.transform_point2(RESOLUTION.size()),
+            Vec2::new(1680.0, 1080.0)
+        );
+        let center = cover
+            .container_to_world(&RESOLUTION)
+            .transform_point2(window / 2.0);
+        assert_eq!(center, RESOLUTION.size() / 2.0);
+        assert_eq!(cover.world_uv_rect(), Rect::new(0.125, 0.0, 0.875, 1.0));
+
+        // Expand fits the world like letterboxing; the space around it is gutters.
+        let expand = layout(window, FitPolicy::Expand);
+        assert_eq!(
+            expand.world_to_container(&RESOLUTION),
+            letterbox.world_to_container(&RESOLUTION)
+        );
+        assert_eq!(expand.world_uv_rect(), Rect::new(0.0, 0.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn letterbox_fits_the_resolution_with_bars() {
+        // A 21:9 window, 2520×1080, fits 960×540 at 2 with 150 virtual pixels on each side.
+        let letterbox = layout(Vec2::new(2520.0, 1080.0), FitPolicy::Letterbox);
+        assert_eq!(letterbox.scale, 2.0);
+        assert_eq!(letterbox.viewport, Vec2::new(1260.0, 540.0));
+        assert_eq!(letterbox.bars, Vec2::new(150.0, 0.0));
+        assert_eq!(letterbox.virtual_size(), RESOLUTION.size());
+        assert_eq!(letterbox.container_size(), Vec2::new(2520.0, 1080.0));
+        assert_eq!(
+            letterbox.virtual_rect(),
+            Rect::new(300.0, 0.0, 2220.0, 1080.0)
+        );
+        assert_eq!(letterbox.world, letterbox.virtual_rect());
+
+        // Below the fit scale, e.g. after rounding, the bars grow on both axes.
+        let rounded = Letterbox::new(Vec2::new(2520.0, 1080.0), &RESOLUTION, 1.5);
+        assert_eq!(rounded.bars, Vec2::new(360.0, 90.0));
+        assert_eq!(rounded.world, letterbox.world);
+    }
+
+    #[test]
+    fn virtual_matrices_agree() {
+        let letterbox = layout(Vec2::new(1440.0, 1080.0), FitPolicy::Letterbox);
+        let corner = RESOLUTION.size();
+        assert_eq!(
+            letterbox.virtual_to_container().transform_point2(corner),
+            Vec2::new(1440.0, 945.0)
+        );
+        assert_eq!(
+            letterbox
+                .container_to_virtual()
+                .transform_point2(Vec2::new(720.0, 540.0)),
+            RESOLUTION.size() / 2.0
+        );
+        assert_eq!(
+            letterbox.virtual_to_uv().transform_point2(Vec2::ZERO),
+            letterbox.virtual_uv_rect().min
+        );
+        assert_eq!(letterbox.virtual_uv_rect().max, Vec2::new(1.0, 0.875));
+        assert_eq!(
+            letterbox.virtual_to_ndc().transform_point2(corner),
+            Vec2::new(1.0, -0.75)
+        );
+    }
+
+    #[test]
+    fn centered_in_keeps_the_layout_in_the_middle() {
+        // A 1000×1000 screen simulated inside a 2000×1000 window.
+        let letterbox = layout(Vec2::new(1000.0, 1000.0), FitPolicy::Letterbox)
+            .centered_in(Vec2::new(2000.0, 1000.0));
+        let scale = 1000.0 / 960.0;
+        assert_eq!(letterbox.scale, scale);
+        assert_eq!(letterbox.container_size(), Vec2::new(2000.0, 1000.0));
+        assert_eq!(letterbox.virtual_size(), RESOLUTION.size());
+        assert_eq!(letterbox.world.center(), Vec2::new(1000.0, 500.0));
+    }
+
+    #[test]
+    fn compensation_shrinks_the_bars_up_to_its_limit() {
+        let letterbox = layout(Vec2::new(2520.0, 1080.0), FitPolicy::Letterbox);
+        let compensated = letterbox.compensated(1.1);
+        assert_eq!(compensated.virtual_size(), Vec2::new(1056.0, 540.0));
+        assert_eq!(compensated.scale, letterbox.scale);
+
+        // Never past the window, nor below the resolution.
+        assert_eq!(letterbox.compensated(2.0).bars, Vec2::ZERO);
+        assert_eq!(letterbox.compensated(0.5), letterbox);
+    }
+
+    #[test]
+    fn gutters_surround_the_expanded_world() {
+        let expand = layout(Vec2::new(2520.0, 1080.0), FitPolicy::Expand);
+        assert_eq!(
+            expand.gutter(AspectRatioMaskSide::Left),
+            Rect::new(0.0, 0.0, 300.0, 1080.0)
+        );
+        assert_eq!(
+            expand.gutter(AspectRatioMaskSide::Right),
+            Rect::new(2220.0, 0.0, 2520.0, 1080.0)
+        );
+        assert_eq!(expand.gutter(AspectRatioMaskSide::Top).height(), 0.0);
+        assert_eq!(
+            expand.world_gutter(AspectRatioMaskSide::Left, &RESOLUTION),
+            Rect::new(-630.0, -270.0, -480.0, 270.0)
+        );
+
+        let cover = layout(Vec2::new(2520.0, 1080.0), FitPolicy::Cover);
+        assert_eq!(cover.gutter(AspectRatioMaskSide::Left).width(), 0.0);
+    }
+}
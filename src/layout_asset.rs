@@ -0,0 +1,341 @@
+use std::collections::HashSet;
+
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext};
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::hud_state::HudState;
+use crate::{HudAnchor, HudStateKey};
+
+/// A HUD described in a RON file, so it can be iterated on without recompiling.
+///
+/// Files with the `.hud.ron` extension are loaded as this asset. Each element is placed in an
+/// anchor slot of the HUD, like a `HudSet`, then shifted by its offset:
+///
+/// ```ron
+/// (
+///     margin: 16.0,
+///     elements: [
+///         (name: "score", anchor: TopLeft, text: Some("Score: 0"), font_size: 24.0),
+///         (
+///             name: "portrait",
+///             anchor: BottomRight,
+///             size: Some((64.0, 64.0)),
+///             image: Some("portrait.png"),
+///         ),
+///         (
+///             name: "hint",
+///             anchor: Bottom,
+///             offset: (0.0, -8.0),
+///             text: Some("Press Space"),
+///             color: Some((1.0, 1.0, 0.6, 1.0)),
+///         ),
+///     ],
+/// )
+/// ```
+///
+/// Offsets and sizes are in virtual pixels, with y pointing down; colors are sRGBA.
+///
+/// Fields are only ever added to the format and unknown fields are ignored, so older files
+/// keep loading. Files may carry a `version` of the game's own choosing (0 when absent), which
+/// a `HudLayoutMigration` uses to upgrade them.
+#[derive(Asset, TypePath, Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct HudLayoutAsset {
+    /// The version of the game's HUD the file was written for.
+    pub version: u32,
+    /// Distance of every element from the HUD's edges, in virtual pixels.
+    pub margin: f32,
+    /// The elements, stacked top to bottom in file order where they share an anchor.
+    pub elements: Vec<HudLayoutEntry>,
+}
+
+/// One element of a `HudLayoutAsset`.
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct HudLayoutEntry {
+    /// Identifies the spawned entity through its `HudLayoutElement`.
+    pub name: String,
+    /// Where the element is placed.
+    pub anchor: HudAnchor,
+    /// Shift from the anchored position.
+    pub offset: [f32; 2],
+    /// The element's size; sized by its content when absent.
+    pub size: Option<[f32; 2]>,
+    /// Text shown in the element.
+    pub text: Option<String>,
+    /// The text's font size.
+    pub font_size: f32,
+    /// The text's color, or the image's tint for elements without text.
+    pub color: Option<[f32; 4]>,
+    /// Path of an image shown in the element, loaded through the `AssetServer`.
+    pub image: Option<String>,
+}
+
+impl Default for HudLayoutEntry {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            anchor: HudAnchor::Center,
+            offset: [0.0, 0.0],
+            size: None,
+            text: None,
+            font_size: 20.0,
+            color: None,
+            image: None,
+        }
+    }
+}
+
+/// Upgrades layout files written for an older version of the game's HUD.
+///
+/// Set it on `AspectRatioPlugin::layout_migration`. Every `HudLayoutAsset` loaded with a
+/// `version` below `version` is passed to `migrate` together with its version, and the
+/// result is stored with `version` set to the current one. This lets games rename elements,
+/// move them between anchors or resize them for new art without breaking layouts saved by
+/// players or mods.
+///
+/// ```rust,no_run
+/// # use bevy_aspect_ratio_mask::{AspectRatioPlugin, HudLayoutAsset, HudLayoutMigration};
+/// fn migrate(version: u32, mut layout: HudLayoutAsset) -> HudLayoutAsset {
+///     if version < 2 {
+///         // Version 2 renamed "hp" to "health".
+///         for element in &mut layout.elements {
+///             if element.name == "hp" {
+///                 element.name = "health".into();
+///             }
+///         }
+///     }
+///     layout
+/// }
+///
+/// let plugin = AspectRatioPlugin {
+///     layout_migration: Some(HudLayoutMigration { version: 2, migrate }),
+///     ..Default::default()
+/// };
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct HudLayoutMigration {
+    /// The current version of the game's HUD.
+    pub version: u32,
+    /// Upgrades a layout of the given older version to the current one.
+    pub migrate: fn(u32, HudLayoutAsset) -> HudLayoutAsset,
+}
+
+/// Loads `.hud.ron` files as `HudLayoutAsset`s, migrating older ones.
+#[derive(Default, TypePath)]
+pub struct HudLayoutLoader {
+    migration: Option<HudLayoutMigration>,
+}
+
+/// An error loading a `HudLayoutAsset`.
+#[derive(Debug)]
+pub enum HudLayoutError {
+    /// The file couldn't be read.
+    Io(std::io::Error),
+    /// The file isn't a valid layout.
+    Ron(ron::error::SpannedError),
+}
+
+impl std::fmt::Display for HudLayoutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HudLayoutError::Io(error) => write!(f, "could not read HUD layout: {error}"),
+            HudLayoutError::Ron(error) => write!(f, "could not parse HUD layout: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for HudLayoutError {}
+
+impl AssetLoader for HudLayoutLoader {
+    type Asset = HudLayoutAsset;
+    type Settings = ();
+    type Error = HudLayoutError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<HudLayoutAsset, HudLayoutError> {
+        let mut bytes = Vec::new();
+        reader
+            .read_to_end(&mut bytes)
+            .await
+            .map_err(HudLayoutError::Io)?;
+        let mut layout: HudLayoutAsset =
+            ron::de::from_bytes(&bytes).map_err(HudLayoutError::Ron)?;
+        if let Some(migration) = self.migration {
+            if layout.version < migration.version {
+                layout = (migration.migrate)(layout.version, layout);
+                layout.version = migration.version;
+            }
+        }
+        Ok(layout)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["hud.ron"]
+    }
+}
+
+/// Registers the layout asset and its loader.
+///
+/// Called from `Plugin::finish`, so it works whether the `AssetPlugin` is added before or
+/// after `AspectRatioPlugin`.
+pub(crate) fn plugin(app: &mut App, migration: Option<HudLayoutMigration>) {
+    // Layout assets need the `AssetPlugin`, which headless apps may leave out.
+    if !app.is_plugin_added::<AssetPlugin>() {
+        warn!("`AssetPlugin` is missing: `HudLayoutAsset` files can't be loaded");
+        return;
+    }
+    app.init_asset::<HudLayoutAsset>()
+        .register_asset_loader(HudLayoutLoader { migration })
+        .add_systems(
+            Update,
+            spawn_hud_layouts.before(crate::hud_state::restore_pending_hud_state),
+        );
+}
+
+/// Spawns the elements of a `HudLayoutAsset`.
+///
+/// Spawn it as a child of the HUD. The elements are respawned whenever the asset changes,
+/// so with Bevy's `file_watcher` feature, saving the file updates the running game. Changing
+/// the handle switches to another layout.
+///
+/// ```rust,no_run
+/// # use bevy::prelude::*;
+/// # use bevy_aspect_ratio_mask::{Hud, HudLayout};
+/// fn setup(mut commands: Commands, hud: Res<Hud>, asset_server: Res<AssetServer>) {
+///     commands
+///         .entity(hud.0)
+///         .with_child(HudLayout(asset_server.load("main.hud.ron")));
+/// }
+/// ```
+#[derive(Component, Clone, Debug, PartialEq)]
+#[require(Node = HudLayout::node(), Pickable = Pickable::IGNORE)]
+pub struct HudLayout(pub Handle<HudLayoutAsset>);
+
+impl HudLayout {
+    fn node() -> Node {
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            ..default()
+        }
+    }
+}
+
+/// An element spawned by a `HudLayout`, named after its `HudLayoutEntry`.
+///
+/// Query it to attach behavior to elements, e.g. to update the text of the `"score"` element.
+/// Elements are respawned when the layout is reloaded, so look them up again afterwards. Each
+/// element also gets a `HudStateKey` with its name, so its runtime state (see `HudState`) is
+/// carried over to the respawned element.
+#[derive(Component, Clone, Debug, PartialEq, Eq)]
+pub struct HudLayoutElement(pub String);
+
+/// Respawns the elements of layouts that were added, switched or reloaded.
+pub(crate) fn spawn_hud_layouts(
+    mut commands: Commands,
+    mut events: MessageReader<AssetEvent<HudLayoutAsset>>,
+    assets: Res<Assets<HudLayoutAsset>>,
+    asset_server: Res<AssetServer>,
+    layouts: Query<(Entity, Ref<HudLayout>)>,
+) {
+    let reloaded: HashSet<AssetId<HudLayoutAsset>> = events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Added { id }
+            | AssetEvent::Modified { id }
+            | AssetEvent::LoadedWithDependencies { id } => Some(*id),
+            _ => None,
+        })
+        .collect();
+
+    for (entity, layout) in layouts.iter() {
+        if !layout.is_changed() && !reloaded.contains(&layout.0.id()) {
+            continue;
+        }
+        // Retried when the asset finishes loading.
+        let Some(asset) = assets.get(&layout.0) else {
+            continue;
+        };
+        // Runtime state such as visibility and running animations survives the reload.
+        commands.queue(move |world: &mut World| {
+            HudState::capture_descendants(world, entity).restore_later(world);
+        });
+        commands.entity(entity).despawn_related::<Children>();
+        spawn_elements(&mut commands, entity, asset, &asset_server);
+    }
+}
+
+fn spawn_elements(
+    commands: &mut Commands,
+    root: Entity,
+    asset: &HudLayoutAsset,
+    asset_server: &AssetServer,
+) {
+    let mut slots: Vec<(HudAnchor, Entity)> = Vec::new();
+    for entry in &asset.elements {
+        let slot = match slots.iter().find(|(anchor, _)| *anchor == entry.anchor) {
+            Some((_, slot)) => *slot,
+            None => {
+                let slot = commands
+                    .spawn((
+                        Name::new("Hud Layout Slot"),
+                        entry.anchor.slot_node(asset.margin),
+                        Pickable::IGNORE,
+                        ChildOf(root),
+                    ))
+                    .id();
+                slots.push((entry.anchor, slot));
+                slot
+            }
+        };
+
+        let size = entry.size.map(Vec2::from);
+        let color = entry
+            .color
+            .map(|[r, g, b, a]| Color::srgba(r, g, b, a))
+            .unwrap_or(Color::WHITE);
+        let mut element = commands.spawn((
+            Name::new(format!("Hud Layout Element {}", entry.name)),
+            HudLayoutElement(entry.name.clone()),
+            HudStateKey::new(entry.name.clone()),
+            Node {
+                left: Val::Px(entry.offset[0]),
+                top: Val::Px(entry.offset[1]),
+                width: size.map_or(Val::Auto, |size| Val::Px(size.x)),
+                height: size.map_or(Val::Auto, |size| Val::Px(size.y)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            ChildOf(slot),
+        ));
+        let text = entry.text.clone().map(|text| {
+            (
+                Text::new(text),
+                TextFont::from_font_size(entry.font_size),
+                TextColor(color),
+            )
+        });
+        match (&entry.image, text) {
+            (Some(image), Some(text)) => {
+                element.insert(ImageNode::new(asset_server.load(image)));
+                element.with_child(text);
+            }
+            (Some(image), None) => {
+                element.insert(ImageNode::new(asset_server.load(image)).with_color(color));
+            }
+            (None, Some(text)) => {
+                element.insert(text);
+            }
+            (None, None) => {}
+        }
+    }
+}
@@ -7,7 +7,7 @@
 //!
 //! ### Example
 //!
-//! ```rust
+//! ```rust,no_run
 //! use bevy::prelude::*;
 //! use bevy_aspect_ratio_mask::{AspectRatioPlugin, Hud};
 //!
@@ -37,35 +37,206 @@
 //!     });
 //! }
 //! ```
+use bevy::a11y::AccessibilitySystems;
 use bevy::color::palettes::tailwind::GRAY_950;
+use bevy::ecs::lifecycle::HookContext;
 use bevy::ecs::schedule::common_conditions::on_message;
+use bevy::ecs::world::DeferredWorld;
+use bevy::input::InputSystems;
 use bevy::prelude::*;
+use bevy::transform::TransformSystems;
+use bevy::ui::{OverrideClip, UiSystems};
+
+mod accessibility;
+mod anchored;
+mod animation;
+mod audit;
+#[cfg(feature = "camera")]
+mod camera;
+mod chrome;
+mod coords;
+mod debug;
+mod dpi;
+#[cfg(feature = "editor")]
+mod editor;
+mod extreme;
+mod focus;
+mod gutters;
+mod hit_area;
+mod hud_state;
+mod layers;
+mod layout;
+#[cfg(feature = "layout_asset")]
+mod layout_asset;
+mod nested;
+mod nine_slice;
+mod orientation;
+mod overlay;
+mod particles;
+mod preview;
+mod quality;
+mod replay;
+mod scaling;
+mod snapshot;
+mod sprites;
+#[cfg(feature = "testing")]
+mod testing;
+mod text_box;
+#[cfg(feature = "camera")]
+mod world_text;
+
+pub use accessibility::AccessibleLabel;
+pub use anchored::{HudAnchor, HudSet, HudSetBuilder};
+pub use animation::{HudScaleAnimation, VirtualTween};
+pub use audit::{HitTestAudit, HitTestOverlap, HitTestReport};
+#[cfg(feature = "camera")]
+pub use camera::AspectRatioCamera;
+pub use chrome::{BarDragRegions, WindowControl, WindowControls};
+pub use coords::{CoordinateChain, ViewportCoords};
+pub use debug::{DebugColors, DebugMeasurement, DebugOverlay, DebugPalette};
+pub use dpi::{EffectiveDpi, ScreenPpi};
+#[cfg(feature = "editor")]
+pub use editor::{EditorBridge, EditorCommand, EditorEvent, EditorHandle};
+pub use extreme::{
+    ActiveExtremeFallback, ExtremeAspectChanged, ExtremeAspectFallback, ExtremeAspectRatio,
+    HudVariant,
+};
+pub use focus::FocusOutline;
+pub use gutters::HudGutters;
+pub use hit_area::HitTargetInflation;
+pub use hud_state::{HudElementState, HudState, HudStateKey};
+pub use layers::{HudLayer, HudLayerKind, InputRouting};
+pub use layout::{AspectRatioContainer, FitPolicy, Letterbox, LetterboxChanged};
+use layout::{LayoutSource, ScreenSimulation};
+#[cfg(feature = "layout_asset")]
+pub use layout_asset::{
+    HudLayout, HudLayoutAsset, HudLayoutElement, HudLayoutEntry, HudLayoutError, HudLayoutLoader,
+    HudLayoutMigration,
+};
+pub use nested::{NestedLetterbox, NestedViewport};
+pub use nine_slice::NineSlicePanel;
+pub use orientation::{OrientationLock, ScreenOrientation};
+pub use overlay::StreamOverlay;
+pub use particles::UiParticleEmitter;
+pub use preview::AspectPreview;
+pub use quality::RenderQualityHint;
+pub use replay::{InputPlayback, InputRecorder, InputRecording, RecordedInput};
+use scaling::ScaleOptions;
+pub use scaling::{
+    ActiveScaleMultiple, AxisCompensation, BitmapFontScaling, DesiredUiScale, ScaleRounding,
+};
+pub use snapshot::LayoutSnapshot;
+pub use sprites::PixelScaledSprite;
+#[cfg(feature = "testing")]
+pub use testing::SyntheticWindowSize;
+pub use text_box::{TextBox, TextBoxPages};
+#[cfg(feature = "camera")]
+pub use world_text::WorldText;
+
+// The `GlobalZIndex` of the plugin's own UI, from top to bottom. Each gets its own value so
+// the stacking stays deterministic when several are shown at once. The window controls stay
+// above the covers so a covered window can still be resized or closed, and a rotate prompt
+// hides a resize cover since rotating the device may already fix the aspect ratio.
+const DEBUG_OVERLAY_Z: i32 = i32::MAX;
+const WINDOW_CONTROLS_Z: i32 = i32::MAX - 1;
+const ROTATE_PROMPT_Z: i32 = i32::MAX - 2;
+const RESIZE_COVER_Z: i32 = i32::MAX - 3;
+const PREVIEW_FRAME_Z: i32 = i32::MAX - 4;
 
 /// A Bevy plugin that enforces a fixed virtual resolution with black bar masking and UI scaling.
 ///
 /// This plugin centers and scales all UI content while hiding out-of-bounds regions
 /// using dynamically positioned black bars. It works best with a 2D camera using
 /// `ScalingMode::AutoMin`.
+#[derive(Default)]
 pub struct AspectRatioPlugin {
     /// The target virtual resolution (default is 960×540).
     pub resolution: Resolution,
+    /// How the world and the HUD are fitted into the window (letterboxing by default).
+    pub policy: FitPolicy,
+    /// Whether `resolution` follows the window until the game locks it (locked by default).
+    pub resolution_mode: ResolutionMode,
     pub mask: AspectRatioMask,
-}
-
-impl Default for AspectRatioPlugin {
-    fn default() -> Self {
-        Self {
-            resolution: Resolution::default(),
-            mask: AspectRatioMask::default(),
-        }
-    }
+    /// Quantizes the UI scale for bitmap fonts when set (disabled by default).
+    pub bitmap_font: Option<BitmapFontScaling>,
+    /// How the UI scale is rounded (not rounded by default).
+    pub scale_rounding: ScaleRounding,
+    /// Publishes the UI scale in `DesiredUiScale` instead of writing `UiScale`, for apps that
+    /// combine several scale sources (disabled by default).
+    pub cooperative_ui_scale: bool,
+    /// Lets the HUD stretch into the bars by a bounded amount when set (experimental,
+    /// disabled by default).
+    pub axis_compensation: Option<AxisCompensation>,
+    /// Limits the supported aspect ratios and picks a fallback past them when set (disabled
+    /// by default).
+    pub extreme_aspect: Option<ExtremeAspectRatio>,
+    /// Covers the game with a "please rotate" prompt in the wrong orientation when set
+    /// (disabled by default).
+    pub orientation_lock: Option<OrientationLock>,
+    /// An existing UI entity to parent the mask and HUD roots under (top level by default).
+    pub parent: Option<Entity>,
+    /// An existing UI node to letterbox the game view inside of, instead of the window.
+    pub container: Option<Entity>,
+    /// Spawns `HudGutters`, HUD containers aligned to the extra world space shown under
+    /// `FitPolicy::Expand` (disabled by default).
+    pub hud_gutters: bool,
+    /// Spawns the bars only while the layout shows them, and despawns them when the window
+    /// matches the resolution exactly (disabled by default). `MaskRegions` only exists while
+    /// the bars do. Useful for kiosk and fixed-resolution deployments.
+    pub lazy_bars: bool,
+    /// Upgrades `HudLayoutAsset`s written for an older version of the HUD when set.
+    #[cfg(feature = "layout_asset")]
+    pub layout_migration: Option<HudLayoutMigration>,
 }
 
 impl Plugin for AspectRatioPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(self.resolution)
-            .insert_resource(self.mask);
+            .insert_resource(self.policy)
+            .insert_resource(self.resolution_mode)
+            .insert_resource(self.scale_rounding)
+            .insert_resource(self.mask.clone());
+        if let Some(bitmap_font) = &self.bitmap_font {
+            app.insert_resource(ActiveScaleMultiple(bitmap_font.active_multiple(0.0)))
+                .insert_resource(bitmap_font.clone());
+        }
+        if let Some(axis_compensation) = self.axis_compensation {
+            app.insert_resource(axis_compensation);
+        }
+        if let Some(extreme_aspect) = &self.extreme_aspect {
+            app.insert_resource(extreme_aspect.clone());
+        }
+        if let Some(orientation_lock) = &self.orientation_lock {
+            app.insert_resource(orientation_lock.clone());
+        }
+        if let Some(parent) = self.parent {
+            app.insert_resource(AspectRatioParent(parent));
+        }
+        if let Some(container) = self.container {
+            app.insert_resource(AspectRatioContainer(container));
+        }
+        if self.lazy_bars {
+            app.insert_resource(LazyBars);
+        }
+        if self.cooperative_ui_scale {
+            app.init_resource::<DesiredUiScale>();
+        }
         plugin(app);
+        if self.hud_gutters {
+            app.add_systems(PreStartup, gutters::spawn_hud_gutters.after(setup))
+                .add_systems(
+                    Update,
+                    gutters::update_hud_gutters
+                        .run_if(on_message::<LetterboxChanged>)
+                        .after(aspect_ratio_hud_scaler),
+                );
+        }
+    }
+
+    // Once all plugins are built, so the `AssetPlugin` may be added after this one.
+    #[cfg(feature = "layout_asset")]
+    fn finish(&self, app: &mut App) {
+        layout_asset::plugin(app, self.layout_migration);
     }
 }
 
@@ -75,24 +246,135 @@ impl Plugin for AspectRatioPlugin {
 /// This color fills the black bars (or any custom color you choose)
 /// when the window's aspect ratio doesn't match the desired resolution.
 /// It's used internally by `AspectRatioPlugin` to visually isolate the game area.
-#[derive(Resource, Clone, Copy)]
+///
+/// Each side can also be given an `ImageNode` (pillarbox art, a vignette, a tiled or
+/// nine-sliced texture) drawn on top of the color. Changing this resource at runtime
+/// updates the bars in place.
+///
+/// Since the images hold asset handles, the mask is `Clone` but not `Copy`.
+#[derive(Resource, Clone)]
 pub struct AspectRatioMask {
     pub color: Color,
+    /// Whether the bars are drawn above or below the HUD (below by default).
+    pub layer: MaskLayer,
+    /// Optional image drawn in the left bar.
+    pub left: Option<ImageNode>,
+    /// Optional image drawn in the right bar.
+    pub right: Option<ImageNode>,
+    /// Optional image drawn in the top bar.
+    pub top: Option<ImageNode>,
+    /// Optional image drawn in the bottom bar.
+    pub bottom: Option<ImageNode>,
+}
+
+impl AspectRatioMask {
+    /// Returns the image configured for `side`, if any.
+    pub fn image(&self, side: AspectRatioMaskSide) -> Option<&ImageNode> {
+        match side {
+            AspectRatioMaskSide::Left => self.left.as_ref(),
+            AspectRatioMaskSide::Right => self.right.as_ref(),
+            AspectRatioMaskSide::Top => self.top.as_ref(),
+            AspectRatioMaskSide::Bottom => self.bottom.as_ref(),
+        }
+    }
 }
 
 impl Default for AspectRatioMask {
     fn default() -> Self {
         Self {
             color: GRAY_950.into(),
+            layer: MaskLayer::default(),
+            left: None,
+            right: None,
+            top: None,
+            bottom: None,
+        }
+    }
+}
+
+/// Where the bars are drawn relative to the HUD.
+///
+/// The bars always cover the world, since UI is drawn over it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MaskLayer {
+    /// Bars are drawn below the HUD, so HUD elements that intentionally extend past the
+    /// virtual area (an ultrawide HUD) are drawn on top of them.
+    #[default]
+    BelowHud,
+    /// Bars are drawn above the HUD, hiding anything that overflows the virtual area.
+    AboveHud,
+}
+
+impl MaskLayer {
+    /// The `ZIndex` given to the mask root, ordering it against the HUD root.
+    fn z_index(self) -> ZIndex {
+        match self {
+            MaskLayer::BelowHud => ZIndex(-1),
+            MaskLayer::AboveHud => ZIndex(1),
         }
     }
 }
 
+/// Marker for a HUD descendant that is allowed to extend into the bars.
+///
+/// The marked node ignores the clipping of its ancestors and is drawn above the bars,
+/// even with `MaskLayer::AboveHud`, while the rest of the HUD stays confined to the
+/// virtual area. Useful for a chat window living in the pillarbox.
+///
+/// Adding the marker inserts `OverrideClip` and `GlobalZIndex(1)` on the entity, unless it
+/// already has them; removing it removes only what it inserted. A `GlobalZIndex` makes the
+/// node a root of its own stacking context, so it is drawn above all UI with a lower global
+/// z-index, not only the bars: in embedded mode that includes the app's panels around the
+/// container. Give the node your own `GlobalZIndex` to place it elsewhere.
+#[derive(Component, Clone, Copy, Debug, Default)]
+#[component(on_add = extend_into_bars_added, on_remove = extend_into_bars_removed)]
+pub struct ExtendIntoBars;
+
+/// The components `ExtendIntoBars` inserted, to be removed with it.
+#[derive(Component)]
+struct ExtendIntoBarsInserted {
+    override_clip: bool,
+    global_z_index: bool,
+}
+
+fn extend_into_bars_added(mut world: DeferredWorld, context: HookContext) {
+    let entity = world.entity(context.entity);
+    let inserted = ExtendIntoBarsInserted {
+        override_clip: !entity.contains::<OverrideClip>(),
+        global_z_index: !entity.contains::<GlobalZIndex>(),
+    };
+    let mut commands = world.commands();
+    let mut entity = commands.entity(context.entity);
+    if inserted.override_clip {
+        entity.insert(OverrideClip);
+    }
+    if inserted.global_z_index {
+        entity.insert(GlobalZIndex(1));
+    }
+    entity.insert(inserted);
+}
+
+fn extend_into_bars_removed(mut world: DeferredWorld, context: HookContext) {
+    let Some(inserted) = world.get::<ExtendIntoBarsInserted>(context.entity) else {
+        return;
+    };
+    let (override_clip, global_z_index) = (inserted.override_clip, inserted.global_z_index);
+    let mut commands = world.commands();
+    let mut entity = commands.entity(context.entity);
+    if override_clip {
+        entity.try_remove::<OverrideClip>();
+    }
+    if global_z_index {
+        entity.try_remove::<GlobalZIndex>();
+    }
+    entity.try_remove::<ExtendIntoBarsInserted>();
+}
+
 /// The virtual resolution used to maintain a consistent aspect ratio.
 ///
 /// This should match your game's design resolution. If the window doesn't
 /// match this ratio, the crate will apply letterboxing and UI scaling automatically.
-#[derive(Resource, Clone, Copy)]
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
 pub struct Resolution {
     /// The target width of the virtual resolution.
     pub width: f32,
@@ -100,6 +382,13 @@ pub struct Resolution {
     pub height: f32,
 }
 
+impl Resolution {
+    /// The virtual resolution as a `Vec2`.
+    pub fn size(&self) -> Vec2 {
+        Vec2::new(self.width, self.height)
+    }
+}
+
 impl Default for Resolution {
     fn default() -> Self {
         Self {
@@ -109,6 +398,32 @@ impl Default for Resolution {
     }
 }
 
+/// Whether `Resolution` is fixed by the game or follows the window.
+///
+/// Games that only know their resolution after boot (e.g. once save settings are read) can
+/// start in `Free` mode: the resolution tracks the window, so there are no bars and the HUD
+/// is drawn 1:1, instead of letterboxing at a default resolution and snapping to the real
+/// one. Switching to `Locked` in the same frame as setting the final `Resolution` engages
+/// letterboxing with a single relayout.
+///
+/// ```rust,no_run
+/// # use bevy::prelude::*;
+/// # use bevy_aspect_ratio_mask::{Resolution, ResolutionMode};
+/// fn apply_saved_settings(mut mode: ResMut<ResolutionMode>, mut resolution: ResMut<Resolution>) {
+///     *resolution = Resolution { width: 640.0, height: 360.0 };
+///     *mode = ResolutionMode::Locked;
+/// }
+/// ```
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ResolutionMode {
+    /// `Resolution` is set by the game and letterboxed into the window.
+    #[default]
+    Locked,
+    /// `Resolution` is overwritten with the window's size (the container's in embedded mode)
+    /// every frame, before the layout.
+    Free,
+}
+
 /// Marker component for the UI node that defines the HUD's layout space.
 ///
 /// Any entities spawned as children of this node will scale and center relative
@@ -119,32 +434,360 @@ struct AspectRatioHud;
 /// Enum identifying one of the four aspect ratio masking regions.
 ///
 /// These are spawned automatically as dark overlays ("black bars") to hide
-/// any extra viewport space when the window aspect ratio deviates.
-#[derive(Component)]
-enum AspectRatioMaskSide {
+/// any extra viewport space when the window aspect ratio deviates. Each bar
+/// node covers exactly its visible region and clips its children, so content
+/// attached to it stays anchored as the bar grows and shrinks.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AspectRatioMaskSide {
     Left,
     Right,
     Top,
     Bottom,
 }
 
+impl AspectRatioMaskSide {
+    /// All four sides, in spawn order.
+    pub const ALL: [AspectRatioMaskSide; 4] = [
+        AspectRatioMaskSide::Left,
+        AspectRatioMaskSide::Right,
+        AspectRatioMaskSide::Top,
+        AspectRatioMaskSide::Bottom,
+    ];
+}
+
+/// Resource pointing to the four mask bar entities.
+///
+/// Spawn children under these entities to decorate the letterbox regions. With
+/// `AspectRatioPlugin::lazy_bars` the resource only exists while the bars are shown, and the
+/// bars are respawned, without their children, each time they reappear.
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct MaskRegions {
+    /// The full-size node holding the four bars.
+    pub root: Entity,
+    pub left: Entity,
+    pub right: Entity,
+    pub top: Entity,
+    pub bottom: Entity,
+}
+
+impl MaskRegions {
+    /// Returns the bar entity for `side`.
+    pub fn get(&self, side: AspectRatioMaskSide) -> Entity {
+        match side {
+            AspectRatioMaskSide::Left => self.left,
+            AspectRatioMaskSide::Right => self.right,
+            AspectRatioMaskSide::Top => self.top,
+            AspectRatioMaskSide::Bottom => self.bottom,
+        }
+    }
+}
+
+/// Resource naming an existing UI entity that the mask and HUD roots are parented under.
+///
+/// Useful when embedding the game view in a larger UI, e.g. under a root with its own
+/// `UiTargetCamera`. The parent should still cover the whole window, since the layout is
+/// computed from the window size. Inserting, changing or removing this resource at runtime
+/// moves the roots accordingly.
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AspectRatioParent(pub Entity);
+
+/// Marker component for the top-level nodes spawned by the plugin (the mask root and the HUD
+/// parent).
+#[derive(Component)]
+struct AspectRatioRoot;
+
+/// Keeps a root hidden until the first layout has been applied to it.
+///
+/// In embedded mode the container's size is only known after the first UI layout, so the
+/// roots would otherwise be drawn for one frame at the unscaled resolution.
+#[derive(Component)]
+struct AwaitingFirstLayout;
+
 /// Resource pointing to the root `Entity` of the aspect-ratio-scaled HUD.
 ///
 /// Use `hud.0` in a system to spawn child nodes that auto-scale and stay centered.
 #[derive(Resource)]
 pub struct Hud(pub Entity);
 
+/// System sets of the plugin, to order your own systems around its layout.
+#[derive(SystemSet, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum AspectRatioSystems {
+    /// Recomputes the `Letterbox`, at most once per frame.
+    ///
+    /// Runs in `Update` in window mode and in `PostUpdate` (after UI layout) in embedded mode.
+    /// Changes to the window size, `Resolution`, `FitPolicy`, `AspectPreview`,
+    /// `BitmapFontScaling`, `AxisCompensation`, `ExtremeAspectRatio` or `ScaleRounding` made
+    /// before this set are coalesced into a single relayout and a single `LetterboxChanged`, so
+    /// a settings menu can apply many of them at once. Changes made after it are applied
+    /// together on the next frame.
+    ///
+    /// In window mode the first layout is already applied in `PostStartup`, so settings made
+    /// in `Startup` are in effect before any `Update` system runs. In embedded mode the
+    /// plugin's nodes stay hidden until the first layout.
+    Relayout,
+}
+
 /// Adds all internal systems for applying aspect ratio masking and UI scaling.
 ///
 /// This is automatically invoked via `AspectRatioPlugin`—you generally don't call this yourself.
 fn plugin(app: &mut App) {
+    app.init_resource::<Letterbox>()
+        .init_resource::<ActiveExtremeFallback>()
+        .init_resource::<EffectiveDpi>()
+        .add_message::<LetterboxChanged>()
+        .add_message::<ExtremeAspectChanged>()
+        .add_message::<RenderQualityHint>()
+        .add_message::<HitTestReport>();
+
     app.add_systems(PreStartup, setup); // PreStartup to register Hud so it can be used in Startup
 
+    // Lay out once before the first `Update`, so settings changed in `Startup` (e.g. the
+    // `Resolution`) are already reflected by the HUD, the bars and `UiScale` on the first frame.
+    app.add_systems(
+        PostStartup,
+        (
+            layout::follow_free_resolution,
+            update_letterbox,
+            sync_lazy_bars.run_if(resource_exists::<LazyBars>),
+            aspect_ratio_hud_scaler
+                .run_if(on_message::<LetterboxChanged>.or(resource_added::<MaskRegions>)),
+        )
+            .chain()
+            .run_if(not(resource_exists::<AspectRatioContainer>)),
+    );
+
     app.add_systems(
         Update,
+        (
+            update_letterbox
+                .run_if(
+                    on_message::<bevy::window::WindowResized>
+                        .or(layout_settings_changed)
+                        .or(resource_changed_or_removed::<AspectPreview>)
+                        .or(resource_removed::<AspectRatioContainer>)
+                        .and(not(resource_exists::<AspectRatioContainer>)),
+                )
+                .in_set(AspectRatioSystems::Relayout),
+            sync_lazy_bars.run_if(
+                resource_exists::<LazyBars>
+                    .and(on_message::<LetterboxChanged>.or(resource_changed::<FitPolicy>)),
+            ),
+            (
+                (
+                    // In embedded mode the scaler follows the relayout in `PostUpdate`.
+                    aspect_ratio_hud_scaler.run_if(
+                        not(resource_exists::<AspectRatioContainer>)
+                            .or(resource_added::<MaskRegions>),
+                    ),
+                    reveal_aspect_ratio_roots,
+                )
+                    .chain()
+                    .run_if(on_message::<LetterboxChanged>.or(resource_added::<MaskRegions>)),
+                quality::send_render_quality_hints.run_if(on_message::<LetterboxChanged>),
+            ),
+        )
+            .chain(),
+    );
+
+    // In embedded mode the container size is only known once this frame's UI layout ran.
+    // Relayout whenever the container's computed size changes, not just on window resizes.
+    app.add_systems(
+        PostUpdate,
+        update_letterbox
+            .after(UiSystems::Layout)
+            .run_if(
+                resource_exists::<AspectRatioContainer>.and(
+                    on_message::<bevy::window::WindowResized>
+                        .or(layout::container_resized)
+                        .or(layout_settings_changed)
+                        .or(resource_changed_or_removed::<AspectPreview>),
+                ),
+            )
+            .in_set(AspectRatioSystems::Relayout),
+    );
+    // Scale the HUD on the frame the container changes, rather than in the next `Update`. The
+    // roots are still revealed in `Update`, once the next layout pass has sized them.
+    app.add_systems(
+        PostUpdate,
         aspect_ratio_hud_scaler
+            .after(AspectRatioSystems::Relayout)
+            .run_if(resource_exists::<AspectRatioContainer>.and(on_message::<LetterboxChanged>)),
+    );
+
+    app.add_systems(
+        Update,
+        layout::follow_free_resolution.before(AspectRatioSystems::Relayout),
+    );
+
+    app.add_systems(
+        Update,
+        reparent_aspect_ratio_roots.run_if(
+            resource_changed_or_removed::<AspectRatioParent>
+                .or(resource_changed_or_removed::<AspectRatioContainer>),
+        ),
+    );
+
+    app.add_systems(
+        Update,
+        (
+            apply_fit_policy.run_if(resource_changed::<FitPolicy>),
+            overlay::sync_stream_overlays,
+        ),
+    );
+    #[cfg(feature = "camera")]
+    app.add_systems(
+        Update,
+        (
+            camera::update_aspect_ratio_cameras,
+            world_text::update_world_text,
+            camera::update_preview_viewports
+                .run_if(
+                    resource_changed_or_removed::<AspectPreview>.or(on_message::<LetterboxChanged>),
+                )
+                .after(AspectRatioSystems::Relayout),
+        ),
+    );
+    #[cfg(feature = "editor")]
+    app.add_systems(
+        Update,
+        editor::apply_editor_commands.run_if(resource_exists::<EditorBridge>),
+    );
+
+    app.add_systems(
+        Update,
+        (
+            extreme::detect_extreme_aspect,
+            (
+                extreme::apply_hud_variants,
+                extreme::update_resize_cover.run_if(
+                    resource_changed::<ActiveExtremeFallback>
+                        .or(resource_exists_and_changed::<ExtremeAspectRatio>),
+                ),
+            ),
+        )
             .chain()
-            .run_if(on_message::<bevy::window::WindowResized>),
+            .before(AspectRatioSystems::Relayout),
+    );
+    app.add_systems(Update, orientation::update_rotate_prompt);
+
+    app.add_observer(chrome::drag_window_from_bars)
+        .add_observer(chrome::stop_window_control_press)
+        .add_observer(chrome::click_window_control)
+        .add_systems(
+            Update,
+            (
+                chrome::sync_window_controls.run_if(
+                    resource_changed_or_removed::<WindowControls>
+                        .or(resource_exists_and_changed::<MaskRegions>),
+                ),
+                chrome::highlight_window_controls,
+            ),
+        );
+
+    app.add_systems(
+        Update,
+        sync_aspect_ratio_mask.run_if(resource_changed::<AspectRatioMask>),
+    );
+
+    app.add_systems(
+        Update,
+        (particles::emit_ui_particles, particles::update_ui_particles).chain(),
+    );
+    app.add_systems(
+        Update,
+        text_box::update_text_boxes.run_if(resource_exists::<bevy::text::TextPipeline>),
+    );
+    app.add_systems(
+        Update,
+        (
+            animation::animate_hud_scale,
+            animation::animate_virtual_tweens.after(AspectRatioSystems::Relayout),
+            (
+                dpi::update_effective_dpi,
+                hit_area::inflate_hit_targets.run_if(resource_exists::<HitTargetInflation>),
+                hit_area::remove_inflated_hit_areas.run_if(resource_removed::<HitTargetInflation>),
+            )
+                .chain()
+                .after(AspectRatioSystems::Relayout),
+            sprites::update_pixel_scaled_sprites.after(AspectRatioSystems::Relayout),
+        ),
+    );
+    app.add_systems(
+        Update,
+        hud_state::restore_pending_hud_state
+            .run_if(resource_exists::<hud_state::PendingHudState>)
+            .before(animation::animate_hud_scale)
+            .before(animation::animate_virtual_tweens)
+            .before(layers::apply_hud_layers),
+    );
+    app.add_systems(
+        Update,
+        (
+            layers::apply_hud_layers,
+            layers::update_hud_layer_shields,
+            layers::update_hud_layer_bar_shields.after(aspect_ratio_hud_scaler),
+            layers::enforce_keyboard_capture,
+        )
+            .chain(),
+    );
+
+    app.add_systems(
+        PreUpdate,
+        replay::play_input
+            .run_if(resource_exists::<InputPlayback>)
+            .before(InputSystems),
+    );
+    app.add_systems(
+        Update,
+        replay::record_input.run_if(resource_exists::<InputRecorder>),
+    );
+
+    app.add_systems(
+        Update,
+        preview::update_aspect_preview_frame
+            .run_if(resource_changed_or_removed::<AspectPreview>.or(on_message::<LetterboxChanged>))
+            .after(aspect_ratio_hud_scaler),
+    );
+    app.add_systems(
+        Update,
+        debug::update_debug_overlay
+            .run_if(resource_changed_or_removed::<DebugOverlay>.or(on_message::<LetterboxChanged>))
+            .after(aspect_ratio_hud_scaler),
+    );
+    app.add_systems(
+        Update,
+        (
+            debug::measure_debug_distance.run_if(resource_exists::<DebugOverlay>),
+            debug::update_debug_measurement.run_if(
+                resource_changed_or_removed::<DebugMeasurement>
+                    .or(resource_changed_or_removed::<DebugOverlay>),
+            ),
+        )
+            .chain(),
+    );
+    app.add_systems(Update, accessibility::apply_accessible_labels);
+    app.add_systems(
+        PostUpdate,
+        accessibility::update_accessible_bounds
+            .after(TransformSystems::Propagate)
+            .before(AccessibilitySystems::Update),
+    );
+
+    app.add_systems(
+        PostUpdate,
+        audit::run_hit_test_audit
+            .run_if(resource_exists::<HitTestAudit>)
+            .after(UiSystems::Stack),
+    );
+
+    app.add_systems(
+        PostUpdate,
+        (
+            nine_slice::update_nine_slice_panels,
+            focus::update_focus_outline,
+            nested::update_nested_viewports,
+        )
+            .after(UiSystems::Layout),
     );
 }
 
@@ -152,74 +795,291 @@ fn setup(
     mut commands: Commands,
     resolution: Res<Resolution>,
     aspect_ration_mask: Res<AspectRatioMask>,
+    container: Option<Res<AspectRatioContainer>>,
+    lazy_bars: Option<Res<LazyBars>>,
 ) {
-    commands.spawn(aspect_ratio_mask_setup(aspect_ration_mask.color));
+    let mask_root = match lazy_bars {
+        // Spawned by `sync_lazy_bars` once the layout needs them.
+        Some(_) => None,
+        None => {
+            let regions = spawn_mask(&mut commands, &aspect_ration_mask);
+            commands.insert_resource(regions);
+            Some(regions.root)
+        }
+    };
 
-    let hud = commands.spawn(aspect_ratio_hud(resolution)).id();
-    let mut base = commands.spawn(aspect_ratio_hud_parent());
+    let hud = commands
+        .spawn((aspect_ratio_hud(resolution), accessibility::region_node()))
+        .id();
+    let mut base = commands.spawn((aspect_ratio_hud_parent(), AspectRatioRoot));
     base.add_child(hud);
+    let base = base.id();
+
+    if container.is_some() {
+        for root in mask_root.into_iter().chain([base]) {
+            commands
+                .entity(root)
+                .insert((Visibility::Hidden, AwaitingFirstLayout));
+        }
+    }
 
     commands.insert_resource(Hud(hud));
 }
 
-/// Updates UI margins and black bars when the window is resized.
+/// Spawns the mask root and its four bars.
+fn spawn_mask(commands: &mut Commands, mask: &AspectRatioMask) -> MaskRegions {
+    let root = commands
+        .spawn((
+            aspect_ratio_hud_parent(),
+            AspectRatioRoot,
+            mask.layer.z_index(),
+            accessibility::hidden_node(),
+        ))
+        .id();
+    let [left, right, top, bottom] = AspectRatioMaskSide::ALL.map(|side| {
+        let mut bar = commands.spawn((aspect_ratio_mask_side(side, mask.color), ChildOf(root)));
+        if let Some(image) = mask.image(side) {
+            bar.insert(image.clone());
+        }
+        bar.id()
+    });
+    MaskRegions {
+        root,
+        left,
+        right,
+        top,
+        bottom,
+    }
+}
+
+/// Marks that `AspectRatioPlugin::lazy_bars` is enabled.
+#[derive(Resource)]
+struct LazyBars;
+
+/// Spawns the bars when the layout shows them and despawns them when it doesn't.
+fn sync_lazy_bars(
+    mut commands: Commands,
+    letterbox: Res<Letterbox>,
+    policy: Res<FitPolicy>,
+    mask: Res<AspectRatioMask>,
+    regions: Option<Res<MaskRegions>>,
+    container: Option<Res<AspectRatioContainer>>,
+    parent: Option<Res<AspectRatioParent>>,
+) {
+    // Bars thinner than half a container pixel aren't visible.
+    let needed =
+        *policy == FitPolicy::Letterbox && (letterbox.bars * letterbox.scale).max_element() >= 0.5;
+    match (needed, regions) {
+        (true, None) => {
+            let regions = spawn_mask(&mut commands, &mask);
+            if let Some(parent) = roots_parent(container.as_deref(), parent.as_deref()) {
+                commands.entity(regions.root).insert(ChildOf(parent));
+            }
+            commands.insert_resource(regions);
+        }
+        (false, Some(regions)) => {
+            commands.entity(regions.root).despawn();
+            commands.remove_resource::<MaskRegions>();
+        }
+        _ => {}
+    }
+}
+
+/// Run condition: a setting the layout depends on changed, e.g. from a settings menu.
+fn layout_settings_changed(
+    resolution: Res<Resolution>,
+    policy: Res<FitPolicy>,
+    bitmap_font: Option<Res<BitmapFontScaling>>,
+    axis_compensation: Option<Res<AxisCompensation>>,
+    extreme_aspect: Option<Res<ExtremeAspectRatio>>,
+    scale_rounding: Res<ScaleRounding>,
+    #[cfg(feature = "testing")] mut synthetic_window: testing::SyntheticWindowChanged,
+) -> bool {
+    #[cfg(feature = "testing")]
+    if synthetic_window.get() {
+        return true;
+    }
+    resolution.is_changed()
+        || policy.is_changed()
+        || bitmap_font.is_some_and(|bitmap_font| bitmap_font.is_changed())
+        || axis_compensation.is_some_and(|axis_compensation| axis_compensation.is_changed())
+        || extreme_aspect.is_some_and(|extreme_aspect| extreme_aspect.is_changed())
+        || scale_rounding.is_changed()
+}
+
+/// Recomputes the `Letterbox` layout when the window is resized.
 ///
-/// Called only when a `WindowResized` event occurs.
-fn aspect_ratio_hud_scaler(
-    windows: Query<&Window>,
+/// Called when the window or container is resized, or when `Resolution` or `FitPolicy` change.
+/// Sends `LetterboxChanged` if the layout differs.
+fn update_letterbox(
+    source: LayoutSource,
     resolution: Res<Resolution>,
-    mut ui_scale: ResMut<UiScale>,
-    mut aspect_ratio_hud: Query<&mut Node, With<AspectRatioHud>>,
-    mut masks: Query<(&AspectRatioMaskSide, &mut Node), Without<AspectRatioHud>>,
+    policy: Res<FitPolicy>,
+    screen: ScreenSimulation,
+    mut options: ScaleOptions,
+    mut current: ResMut<Letterbox>,
+    mut letterbox_changed: MessageWriter<LetterboxChanged>,
 ) {
-    let scale_x = windows.single().unwrap().resolution.size().x / resolution.width;
-    let scale_y = windows.single().unwrap().resolution.size().y / resolution.height;
+    let Some(window_size) = source.size() else {
+        return;
+    };
+    // Under an `AspectPreview` or a clamped extreme aspect ratio, lay out for the simulated
+    // screen first.
+    let screen_size = screen.layout_size(window_size);
 
-    let normalized_width = resolution.width * scale_x / scale_y;
-    let normalized_height = resolution.height * scale_y / scale_x;
+    let scale = options.scale(
+        Letterbox::fit_scale(screen_size, &resolution),
+        source.physical_scale().unwrap_or(1.0),
+    );
+    let letterbox = options
+        .adjust(Letterbox::new(screen_size, &resolution, scale))
+        .with_policy(*policy, &resolution)
+        .centered_in(window_size);
 
-    let min_scale = scale_x.min(scale_y);
+    if *current != letterbox {
+        letterbox_changed.write(LetterboxChanged {
+            previous: *current,
+            current: letterbox,
+        });
+        *current = letterbox;
+    }
+}
 
-    let Ok(mut node) = aspect_ratio_hud.single_mut() else {
+/// Updates UI margins and black bars from the current `Letterbox`.
+///
+/// Called only when a `LetterboxChanged` event occurs.
+///
+/// In window mode the HUD is scaled through `UiScale` (or `DesiredUiScale` in cooperative
+/// mode), so one UI pixel is one virtual pixel. In embedded mode `UiScale` is left alone and
+/// the HUD is scaled around its center with a `UiTransform` instead, so bars and margins are
+/// expressed in the container's UI pixels.
+fn aspect_ratio_hud_scaler(
+    letterbox: Res<Letterbox>,
+    container: Option<Res<AspectRatioContainer>>,
+    mut ui_scale: ResMut<UiScale>,
+    desired_ui_scale: Option<ResMut<DesiredUiScale>>,
+    mut aspect_ratio_hud: Query<(&mut Node, &mut UiTransform), With<AspectRatioHud>>,
+    mut masks: Query<(&AspectRatioMaskSide, &mut Node), Without<AspectRatioHud>>,
+) {
+    let Ok((mut node, mut transform)) = aspect_ratio_hud.single_mut() else {
         return;
     };
 
-    let dx = normalized_width - resolution.width;
-    if scale_x > min_scale {
-        node.margin.left = Val::Px(dx / 2.0);
-    } else if scale_x <= min_scale {
-        node.margin.left = Val::Px(0.0);
-    }
+    let node_scale = match container {
+        Some(_) => letterbox.scale,
+        None => {
+            match desired_ui_scale {
+                Some(mut desired) => desired.0 = letterbox.scale,
+                None => ui_scale.0 = letterbox.scale,
+            }
+            1.0
+        }
+    };
+    let area = letterbox.viewport * node_scale;
+    let bars = letterbox.bars * node_scale;
+    let size = letterbox.virtual_size();
 
-    let dy = normalized_height - resolution.height;
-    if scale_y > min_scale {
-        node.margin.top = Val::Px(dy / 2.0);
-    } else if scale_y <= min_scale {
-        node.margin.top = Val::Px(0.0);
-    }
+    // Centered in the container; the transform scales the HUD around that center.
+    node.width = Val::Px(size.x);
+    node.height = Val::Px(size.y);
+    node.margin.left = Val::Px((area.x - size.x) / 2.0);
+    node.margin.top = Val::Px((area.y - size.y) / 2.0);
+    transform.scale = Vec2::splat(node_scale);
 
+    // Each bar covers only its visible region so attached content lines up.
     for (mask, mut node) in masks.iter_mut() {
         match mask {
             AspectRatioMaskSide::Left => {
-                node.width = Val::Px(dx);
-                node.left = Val::Px(-dx / 2.0);
+                node.width = Val::Px(bars.x);
+                node.left = Val::Px(0.0);
             }
             AspectRatioMaskSide::Right => {
-                node.width = Val::Px(dx);
-                node.left = Val::Px(normalized_width - dx / 2.0);
+                node.width = Val::Px(bars.x);
+                node.left = Val::Px(area.x - bars.x);
             }
             AspectRatioMaskSide::Top => {
-                node.height = Val::Px(dy);
-                node.top = Val::Px(-dy / 2.0);
+                node.height = Val::Px(bars.y);
+                node.top = Val::Px(0.0);
             }
             AspectRatioMaskSide::Bottom => {
-                node.height = Val::Px(dy);
-                node.top = Val::Px(normalized_height - dy / 2.0);
+                node.height = Val::Px(bars.y);
+                node.top = Val::Px(area.y - bars.y);
             }
         }
     }
+}
+
+/// The entity the plugin's roots are parented under, if any.
+fn roots_parent(
+    container: Option<&AspectRatioContainer>,
+    parent: Option<&AspectRatioParent>,
+) -> Option<Entity> {
+    container
+        .map(|container| container.0)
+        .or(parent.map(|parent| parent.0))
+}
 
-    ui_scale.0 = min_scale;
+/// Shows the roots that were hidden until the first layout.
+fn reveal_aspect_ratio_roots(
+    mut commands: Commands,
+    mut roots: Query<(Entity, &mut Visibility), With<AwaitingFirstLayout>>,
+) {
+    for (root, mut visibility) in roots.iter_mut() {
+        *visibility = Visibility::Inherited;
+        commands.entity(root).remove::<AwaitingFirstLayout>();
+    }
+}
+
+/// Moves the plugin's root nodes under the `AspectRatioContainer` or `AspectRatioParent`,
+/// or back to the top level.
+fn reparent_aspect_ratio_roots(
+    mut commands: Commands,
+    container: Option<Res<AspectRatioContainer>>,
+    parent: Option<Res<AspectRatioParent>>,
+    roots: Query<Entity, With<AspectRatioRoot>>,
+) {
+    let parent = roots_parent(container.as_deref(), parent.as_deref());
+    for root in roots.iter() {
+        match parent {
+            Some(parent) => commands.entity(root).insert(ChildOf(parent)),
+            None => commands.entity(root).remove::<ChildOf>(),
+        };
+    }
+}
+
+/// Shows the bars only for policies that mask the leftover space.
+fn apply_fit_policy(
+    policy: Res<FitPolicy>,
+    regions: Option<Res<MaskRegions>>,
+    mut nodes: Query<&mut Node>,
+) {
+    let Some(mut root) = regions.and_then(|regions| nodes.get_mut(regions.root).ok()) else {
+        return;
+    };
+    root.display = match *policy {
+        FitPolicy::Letterbox => Display::Flex,
+        FitPolicy::Cover | FitPolicy::Expand => Display::None,
+    };
+}
+
+/// Applies runtime changes of `AspectRatioMask` to the existing bars.
+fn sync_aspect_ratio_mask(
+    mut commands: Commands,
+    mask: Res<AspectRatioMask>,
+    regions: Option<Res<MaskRegions>>,
+    mut bars: Query<(Entity, &AspectRatioMaskSide, &mut BackgroundColor)>,
+) {
+    if let Some(regions) = regions {
+        commands.entity(regions.root).insert(mask.layer.z_index());
+    }
+
+    for (entity, side, mut background) in bars.iter_mut() {
+        background.0 = mask.color;
+        match mask.image(*side) {
+            Some(image) => commands.entity(entity).insert(image.clone()),
+            None => commands.entity(entity).remove::<ImageNode>(),
+        };
+    }
 }
 
 /// Spawns a 100% sized container node for holding HUD content.
@@ -255,57 +1115,31 @@ fn aspect_ratio_hud(resolution: Res<Resolution>) -> impl Bundle {
     )
 }
 
-/// Spawns four masking nodes that surround the viewport to simulate black bars.
+/// Creates one masking node that covers a side of the viewport to simulate a black bar.
 ///
-/// These are automatically sized based on the window and resolution mismatch.
-fn aspect_ratio_mask_setup(color: Color) -> impl Bundle {
+/// These are spawned under a full-size parent and automatically sized based on the
+/// window and resolution mismatch.
+fn aspect_ratio_mask_side(side: AspectRatioMaskSide, color: Color) -> impl Bundle {
+    let node = match side {
+        AspectRatioMaskSide::Left | AspectRatioMaskSide::Right => Node {
+            height: Val::Percent(100.0),
+            left: Val::Px(0.0),
+            position_type: PositionType::Absolute,
+            overflow: Overflow::clip(),
+            ..default()
+        },
+        AspectRatioMaskSide::Top | AspectRatioMaskSide::Bottom => Node {
+            width: Val::Percent(100.0),
+            top: Val::Px(0.0),
+            position_type: PositionType::Absolute,
+            overflow: Overflow::clip(),
+            ..default()
+        },
+    };
     (
-        aspect_ratio_hud_parent(),
-        children![
-            (
-                AspectRatioMaskSide::Left,
-                Name::new("Aspect Ratio Mask"),
-                Node {
-                    height: Val::Percent(100.0),
-                    left: Val::Px(0.0),
-                    position_type: PositionType::Absolute,
-                    ..default()
-                },
-                BackgroundColor(color),
-            ),
-            (
-                AspectRatioMaskSide::Right,
-                Name::new("Aspect Ratio Mask"),
-                Node {
-                    height: Val::Percent(100.0),
-                    left: Val::Px(0.0),
-                    position_type: PositionType::Absolute,
-                    ..default()
-                },
-                BackgroundColor(color),
-            ),
-            (
-                AspectRatioMaskSide::Top,
-                Name::new("Aspect Ratio Mask"),
-                Node {
-                    width: Val::Percent(100.0),
-                    top: Val::Px(0.0),
-                    position_type: PositionType::Absolute,
-                    ..default()
-                },
-                BackgroundColor(color),
-            ),
-            (
-                AspectRatioMaskSide::Bottom,
-                Name::new("Aspect Ratio Mask"),
-                Node {
-                    width: Val::Percent(100.0),
-                    top: Val::Px(0.0),
-                    position_type: PositionType::Absolute,
-                    ..default()
-                },
-                BackgroundColor(color),
-            )
-        ],
+        side,
+        Name::new("Aspect Ratio Mask"),
+        node,
+        BackgroundColor(color),
     )
 }
@@ -0,0 +1,173 @@
+use bevy::ecs::lifecycle::HookContext;
+use bevy::ecs::world::DeferredWorld;
+use bevy::prelude::*;
+use bevy::ui::ComputedNode;
+
+use crate::{accessibility, AspectRatioMask, AspectRatioMaskSide, Letterbox, Resolution};
+
+/// A letterboxed region with its own virtual resolution, nested inside another UI node.
+///
+/// Spawn it as the only child of a node that defines the available area, such as the
+/// screen of an in-game arcade cabinet drawn on the HUD. The plugin fits the viewport
+/// into its parent, scales it with a `UiTransform` and masks the leftover space with
+/// bars, exactly like the top-level HUD. Children of the viewport are laid out in its
+/// own virtual pixels, and viewports can be nested inside each other.
+///
+/// The viewport's `Node` size and position are managed by the plugin; size its parent instead.
+/// Use `ViewportCoords` to map window positions into the viewport.
+///
+/// ```rust,no_run
+/// # use bevy::prelude::*;
+/// # use bevy_aspect_ratio_mask::{Hud, NestedViewport, Resolution};
+/// fn setup(mut commands: Commands, hud: Res<Hud>) {
+///     commands.entity(hud.0).with_child((
+///         Node {
+///             width: Val::Px(320.0),
+///             height: Val::Px(240.0),
+///             ..default()
+///         },
+///         children![(
+///             NestedViewport::new(Resolution { width: 256.0, height: 224.0 }),
+///             children![Text::new("INSERT COIN")],
+///         )],
+///     ));
+/// }
+/// ```
+#[derive(Component, Clone, Copy, Debug)]
+#[require(Node)]
+#[component(on_add = spawn_nested_viewport_bars)]
+pub struct NestedViewport {
+    /// The virtual resolution of the nested region.
+    pub resolution: Resolution,
+    /// The color of the nested region's bars.
+    pub color: Color,
+}
+
+impl NestedViewport {
+    /// Creates a nested viewport with the default mask color.
+    pub fn new(resolution: Resolution) -> Self {
+        Self {
+            resolution,
+            color: AspectRatioMask::default().color,
+        }
+    }
+}
+
+/// The layout currently applied to a `NestedViewport`, in the viewport's virtual pixels.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Default, Deref)]
+pub struct NestedLetterbox(pub Letterbox);
+
+/// One of the bars of a `NestedViewport`, spawned as a child of the viewport.
+#[derive(Component, Clone, Copy, Debug)]
+pub(crate) struct NestedViewportBar(AspectRatioMaskSide);
+
+fn spawn_nested_viewport_bars(mut world: DeferredWorld, context: HookContext) {
+    let color = world
+        .get::<NestedViewport>(context.entity)
+        .map_or(Color::BLACK, |viewport| viewport.color);
+    let mut commands = world.commands();
+    commands
+        .entity(context.entity)
+        .insert_if_new(accessibility::region_node());
+    for side in AspectRatioMaskSide::ALL {
+        commands.spawn((
+            NestedViewportBar(side),
+            Name::new("Nested Viewport Mask"),
+            Node {
+                position_type: PositionType::Absolute,
+                overflow: Overflow::clip(),
+                ..default()
+            },
+            // Draw above the viewport's own children so overflowing content stays hidden.
+            ZIndex(i32::MAX),
+            BackgroundColor(color),
+            ChildOf(context.entity),
+        ));
+    }
+}
+
+/// Fits every nested viewport into its parent's computed size.
+///
+/// Runs after layout, so changes are applied on the next layout pass.
+#[allow(clippy::type_complexity)]
+pub(crate) fn update_nested_viewports(
+    mut viewports: Query<(
+        Entity,
+        Ref<NestedViewport>,
+        &ChildOf,
+        &mut Node,
+        &mut UiTransform,
+        Option<&mut NestedLetterbox>,
+    )>,
+    parents: Query<Ref<ComputedNode>>,
+    mut bars: Query<
+        (
+            &NestedViewportBar,
+            &ChildOf,
+            &mut Node,
+            &mut BackgroundColor,
+        ),
+        Without<NestedViewport>,
+    >,
+    mut commands: Commands,
+) {
+    for (entity, viewport, child_of, mut node, mut transform, current) in viewports.iter_mut() {
+        let Ok(parent) = parents.get(child_of.parent()) else {
+            continue;
+        };
+        let unchanged = current.is_some() && !viewport.is_changed() && !parent.is_changed();
+        let size = parent.size() * parent.inverse_scale_factor();
+        if unchanged || size.min_element() <= 0.0 {
+            continue;
+        }
+
+        let resolution = viewport.resolution;
+        let letterbox = Letterbox::new(size, &resolution, Letterbox::fit_scale(size, &resolution));
+        match current {
+            Some(mut current) => current.set_if_neq(NestedLetterbox(letterbox)),
+            None => {
+                commands.entity(entity).insert(NestedLetterbox(letterbox));
+                true
+            }
+        };
+
+        // Centered in the parent; the transform scales the viewport around that center.
+        node.position_type = PositionType::Absolute;
+        node.width = Val::Px(resolution.width);
+        node.height = Val::Px(resolution.height);
+        node.left = Val::Px((size.x - resolution.width) / 2.0);
+        node.top = Val::Px((size.y - resolution.height) / 2.0);
+        transform.scale = Vec2::splat(letterbox.scale);
+
+        // Bars hang outside the viewport's own rect, in its virtual pixels.
+        let bar = letterbox.bars;
+        for (side, bar_parent, mut bar_node, mut background) in bars.iter_mut() {
+            if bar_parent.parent() != entity {
+                continue;
+            }
+            background.0 = viewport.color;
+            let (left, top, width, height) = match side.0 {
+                AspectRatioMaskSide::Left => {
+                    (-bar.x, -bar.y, bar.x, resolution.height + 2.0 * bar.y)
+                }
+                AspectRatioMaskSide::Right => (
+                    resolution.width,
+                    -bar.y,
+                    bar.x,
+                    resolution.height + 2.0 * bar.y,
+                ),
+                AspectRatioMaskSide::Top => (-bar.x, -bar.y, resolution.width + 2.0 * bar.x, bar.y),
+                AspectRatioMaskSide::Bottom => (
+                    -bar.x,
+                    resolution.height,
+                    resolution.width + 2.0 * bar.x,
+                    bar.y,
+                ),
+            };
+            bar_node.left = Val::Px(left);
+            bar_node.top = Val::Px(top);
+            bar_node.width = Val::Px(width);
+            bar_node.height = Val::Px(height);
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use bevy::prelude::*;
+use bevy::ui::ComputedNode;
+
+/// A nine-slice panel whose border thickness is specified in virtual pixels.
+///
+/// Bevy draws the corners of a sliced image at their texture size in physical pixels, so a
+/// plain `NodeImageMode::Sliced` border shrinks relative to the HUD as `UiScale` grows and gets
+/// resampled into blurry or chunky edges at fractional scales. This panel rescales the slicer
+/// every layout so the border stays `thickness` virtual pixels wide, snapped to whole physical
+/// pixels.
+///
+/// Spawn it as a child of the HUD and size it with its `Node` as usual:
+///
+/// ```rust,no_run
+/// # use bevy::prelude::*;
+/// # use bevy_aspect_ratio_mask::{Hud, NineSlicePanel};
+/// fn setup(mut commands: Commands, hud: Res<Hud>, assets: Res<AssetServer>) {
+///     commands.entity(hud.0).with_child((
+///         NineSlicePanel::new(assets.load("panel.png"), 8.0, 6.0),
+///         Node {
+///             width: Val::Px(200.0),
+///             height: Val::Px(120.0),
+///             ..default()
+///         },
+///     ));
+/// }
+/// ```
+#[derive(Component, Clone)]
+#[require(Node)]
+pub struct NineSlicePanel {
+    /// The sliced texture.
+    pub image: Handle<Image>,
+    /// The inset, in texture pixels, of the slicing lines on every side of the texture.
+    pub slice: f32,
+    /// The rendered border thickness, in virtual pixels.
+    pub thickness: f32,
+    /// How the center slice fills the panel.
+    pub center_scale_mode: SliceScaleMode,
+    /// How the four side slices fill the panel.
+    pub sides_scale_mode: SliceScaleMode,
+}
+
+impl NineSlicePanel {
+    /// Creates a stretched panel from a texture, its slice inset in texture pixels, and the
+    /// border thickness in virtual pixels.
+    pub fn new(image: Handle<Image>, slice: f32, thickness: f32) -> Self {
+        Self {
+            image,
+            slice,
+            thickness,
+            center_scale_mode: SliceScaleMode::Stretch,
+            sides_scale_mode: SliceScaleMode::Stretch,
+        }
+    }
+
+    /// Builds the slicer for a node laid out at `layout_scale` physical pixels per virtual
+    /// pixel and drawn scaled by `transform_scale` (its and its ancestors' `UiTransform`s).
+    fn slicer(&self, layout_scale: f32, transform_scale: f32) -> TextureSlicer {
+        let physical_thickness = (self.thickness * layout_scale * transform_scale)
+            .round()
+            .max(1.0);
+        // Slices are sized in the node's layout space, before the transform is applied.
+        let layout_thickness = physical_thickness / transform_scale.max(f32::EPSILON);
+        TextureSlicer {
+            border: BorderRect::all(self.slice),
+            center_scale_mode: self.center_scale_mode,
+            sides_scale_mode: self.sides_scale_mode,
+            max_corner_scale: layout_thickness / self.slice.max(f32::EPSILON),
+        }
+    }
+}
+
+/// Keeps each panel's `ImageNode` slicer in sync with its current physical scale.
+///
+/// Runs after layout so the scale read from `ComputedNode` includes this frame's `UiScale`.
+/// The scale of `UiGlobalTransform` covers HUDs scaled by a transform instead, as in embedded
+/// mode or during a `HudScaleAnimation`.
+#[allow(clippy::type_complexity)]
+pub(crate) fn update_nine_slice_panels(
+    mut commands: Commands,
+    mut panels: Query<
+        (
+            Entity,
+            &NineSlicePanel,
+            &ComputedNode,
+            &UiGlobalTransform,
+            Option<&mut ImageNode>,
+        ),
+        Or<(
+            Changed<NineSlicePanel>,
+            Changed<ComputedNode>,
+            Changed<UiGlobalTransform>,
+        )>,
+    >,
+) {
+    for (entity, panel, computed, transform, image_node) in panels.iter_mut() {
+        let (transform_scale, _, _) = transform.to_scale_angle_translation();
+        let mode = NodeImageMode::Sliced(panel.slicer(
+            computed.inverse_scale_factor.recip(),
+            transform_scale.x.abs(),
+        ));
+
+        match image_node {
+            Some(mut image_node) => {
+                if image_node.image != panel.image {
+                    image_node.image = panel.image.clone();
+                }
+                if image_node.image_mode != mode {
+                    image_node.image_mode = mode;
+                }
+            }
+            None => {
+                commands
+                    .entity(entity)
+                    .insert(ImageNode::new(panel.image.clone()).with_mode(mode));
+            }
+        }
+    }
+}
@@ -0,0 +1,95 @@
+use bevy::prelude::*;
+
+use crate::extreme::prompt_cover;
+use crate::layout::{LayoutSource, ScreenSimulation};
+use crate::{Hud, ROTATE_PROMPT_Z};
+
+/// Whether a screen is taller or wider.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ScreenOrientation {
+    /// Taller than wide.
+    Portrait,
+    /// Wider than tall.
+    #[default]
+    Landscape,
+}
+
+impl ScreenOrientation {
+    /// Whether a screen of `size` has this orientation. Square screens have both.
+    pub fn matches(self, size: Vec2) -> bool {
+        match self {
+            ScreenOrientation::Portrait => size.y >= size.x,
+            ScreenOrientation::Landscape => size.x >= size.y,
+        }
+    }
+}
+
+/// Locks the game to one orientation, covering it with a "please rotate" prompt otherwise.
+///
+/// Meant for portrait-only or landscape-only mobile games. The prompt is shown over the game
+/// UI, including a resize cover, while the window's orientation doesn't match `supported`,
+/// and removed as soon as the device is rotated back. Under an `AspectPreview` the simulated
+/// screen is checked instead.
+#[derive(Resource, Clone, Debug, PartialEq)]
+pub struct OrientationLock {
+    /// The orientation the game is designed for.
+    pub supported: ScreenOrientation,
+    /// The message shown in the prompt.
+    pub prompt_text: String,
+    /// The background of the prompt.
+    pub prompt_color: Color,
+}
+
+impl OrientationLock {
+    /// Locks the game to `supported`, with the default prompt.
+    pub fn new(supported: ScreenOrientation) -> Self {
+        Self {
+            supported,
+            prompt_text: "Please rotate your device".to_string(),
+            prompt_color: Color::BLACK,
+        }
+    }
+}
+
+impl Default for OrientationLock {
+    fn default() -> Self {
+        Self::new(ScreenOrientation::default())
+    }
+}
+
+/// The prompt spawned by `OrientationLock`.
+#[derive(Component)]
+pub(crate) struct RotatePrompt;
+
+/// Shows the rotate prompt while the screen's orientation isn't the supported one.
+pub(crate) fn update_rotate_prompt(
+    mut commands: Commands,
+    source: LayoutSource,
+    screen: ScreenSimulation,
+    lock: Option<Res<OrientationLock>>,
+    hud: Res<Hud>,
+    parents: Query<&ChildOf>,
+    prompts: Query<Entity, With<RotatePrompt>>,
+) {
+    let Some(size) = source.size().map(|size| screen.screen_size(size)) else {
+        return;
+    };
+    let lock = lock.filter(|lock| !lock.supported.matches(size));
+    let settings_changed = lock.as_ref().is_some_and(|lock| lock.is_changed());
+    if lock.is_some() != prompts.is_empty() && !settings_changed {
+        return;
+    }
+    for prompt in prompts.iter() {
+        commands.entity(prompt).despawn();
+    }
+    let (Some(lock), Ok(base)) = (lock, parents.get(hud.0)) else {
+        return;
+    };
+
+    commands.spawn((
+        Name::new("Rotate Prompt"),
+        RotatePrompt,
+        prompt_cover(lock.prompt_text.clone(), lock.prompt_color, ROTATE_PROMPT_Z),
+        ChildOf(base.parent()),
+    ));
+}
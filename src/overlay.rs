@@ -0,0 +1,110 @@
+use bevy::ecs::lifecycle::HookContext;
+use bevy::ecs::world::DeferredWorld;
+use bevy::prelude::*;
+
+use crate::{AspectRatioMask, NestedViewport, Resolution};
+
+/// A spectator or stream overlay rendered only to a secondary camera's target.
+///
+/// Point `camera` at a camera that renders to a second window or to an image (e.g. one
+/// captured for streaming). The overlay becomes a letterboxed area with its own virtual
+/// resolution, fitted into that target exactly like the HUD is fitted into the primary
+/// window, while the player's view and HUD are left unchanged. Children of the overlay are
+/// laid out in its virtual pixels.
+///
+/// ```rust,no_run
+/// # use bevy::prelude::*;
+/// # use bevy_aspect_ratio_mask::{Resolution, StreamOverlay};
+/// fn setup(mut commands: Commands, stream_camera: Single<Entity, With<Camera2d>>) {
+///     commands.spawn((
+///         StreamOverlay::new(*stream_camera, Resolution { width: 1920.0, height: 1080.0 }),
+///         children![Text::new("LIVE")],
+///     ));
+/// }
+/// ```
+///
+/// The overlay is reparented under a root node targeting `camera`, so spawn it without a
+/// parent. Despawning the overlay also despawns that root.
+#[derive(Component, Clone, Copy, Debug)]
+#[require(Node)]
+#[component(on_add = spawn_stream_overlay_root, on_remove = despawn_stream_overlay_root)]
+pub struct StreamOverlay {
+    /// The camera whose render target shows the overlay.
+    pub camera: Entity,
+    /// The virtual resolution of the overlay.
+    pub resolution: Resolution,
+    /// The color of the overlay's bars.
+    pub color: Color,
+}
+
+impl StreamOverlay {
+    /// Creates an overlay for `camera` with the default mask color.
+    pub fn new(camera: Entity, resolution: Resolution) -> Self {
+        Self {
+            camera,
+            resolution,
+            color: AspectRatioMask::default().color,
+        }
+    }
+}
+
+/// The full-target root node a `StreamOverlay` is fitted into.
+#[derive(Component, Clone, Copy, Debug)]
+pub(crate) struct StreamOverlayRoot(Entity);
+
+fn spawn_stream_overlay_root(mut world: DeferredWorld, context: HookContext) {
+    let Some(overlay) = world.get::<StreamOverlay>(context.entity).copied() else {
+        return;
+    };
+    let mut commands = world.commands();
+    let root = commands
+        .spawn((
+            StreamOverlayRoot(context.entity),
+            Name::new("Stream Overlay Root"),
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            UiTargetCamera(overlay.camera),
+        ))
+        .id();
+    commands.entity(context.entity).insert((
+        NestedViewport {
+            resolution: overlay.resolution,
+            color: overlay.color,
+        },
+        ChildOf(root),
+    ));
+}
+
+fn despawn_stream_overlay_root(mut world: DeferredWorld, context: HookContext) {
+    let Some(root) = world
+        .get::<ChildOf>(context.entity)
+        .map(ChildOf::parent)
+        .filter(|parent| {
+            world
+                .get::<StreamOverlayRoot>(*parent)
+                .is_some_and(|root| root.0 == context.entity)
+        })
+    else {
+        return;
+    };
+    world.commands().entity(root).try_despawn();
+}
+
+/// Keeps each overlay's nested viewport in sync with runtime changes of the overlay.
+pub(crate) fn sync_stream_overlays(
+    mut overlays: Query<(&StreamOverlay, &mut NestedViewport), Changed<StreamOverlay>>,
+    mut roots: Query<(&StreamOverlayRoot, &mut UiTargetCamera)>,
+) {
+    for (overlay, mut viewport) in overlays.iter_mut() {
+        viewport.resolution = overlay.resolution;
+        viewport.color = overlay.color;
+    }
+    for (root, mut target) in roots.iter_mut() {
+        if let Ok((overlay, _)) = overlays.get(root.0) {
+            target.set_if_neq(UiTargetCamera(overlay.camera));
+        }
+    }
+}
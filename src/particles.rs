@@ -0,0 +1,211 @@
+use std::f32::consts::{FRAC_PI_2, PI};
+
+use bevy::prelude::*;
+
+/// A lightweight UI particle emitter whose positions and velocities are in virtual pixels.
+///
+/// Spawn it as a direct child of the HUD. By default the emitter node covers the whole
+/// virtual area and clips its particles to it, so reward screens and confetti bursts look
+/// identical at every window size. Particles are plain UI nodes spawned as children of
+/// the emitter, and the random stream is seeded, so the same emitter always produces the
+/// same pattern.
+///
+/// ```rust,no_run
+/// # use bevy::prelude::*;
+/// # use bevy_aspect_ratio_mask::{Hud, UiParticleEmitter};
+/// fn celebrate(mut commands: Commands, hud: Res<Hud>) {
+///     commands
+///         .entity(hud.0)
+///         .with_child(UiParticleEmitter::confetti(Vec2::new(480.0, 200.0)));
+/// }
+/// ```
+#[derive(Component, Clone, Debug)]
+#[require(
+    Node = UiParticleEmitter::node(),
+    Pickable = Pickable::IGNORE,
+    UiParticleEmitterState
+)]
+pub struct UiParticleEmitter {
+    /// Where particles are emitted, in virtual pixels from the emitter's top-left corner.
+    pub origin: Vec2,
+    /// Particles emitted per second.
+    pub rate: f32,
+    /// Particles still to be emitted at once on the next update.
+    pub burst: u32,
+    /// How long each particle lives, in seconds.
+    pub lifetime: f32,
+    /// The slowest initial speed, in virtual pixels per second.
+    pub min_speed: f32,
+    /// The fastest initial speed, in virtual pixels per second.
+    pub max_speed: f32,
+    /// The mean emission angle in radians, measured clockwise from the +x axis (y points down).
+    pub direction: f32,
+    /// The total emission cone in radians, centered on `direction`.
+    pub spread: f32,
+    /// Acceleration applied to every particle, in virtual pixels per second squared.
+    pub gravity: Vec2,
+    /// The side length of each particle, in virtual pixels.
+    pub size: f32,
+    /// Colors picked at random for each particle. Particles fade out over their lifetime.
+    pub colors: Vec<Color>,
+    /// Seed of the emitter's random stream.
+    pub seed: u32,
+}
+
+impl Default for UiParticleEmitter {
+    fn default() -> Self {
+        Self {
+            origin: Vec2::ZERO,
+            rate: 0.0,
+            burst: 0,
+            lifetime: 1.0,
+            min_speed: 50.0,
+            max_speed: 100.0,
+            direction: -FRAC_PI_2,
+            spread: PI / 4.0,
+            gravity: Vec2::ZERO,
+            size: 4.0,
+            colors: vec![Color::WHITE],
+            seed: 1,
+        }
+    }
+}
+
+impl UiParticleEmitter {
+    /// A one-shot confetti burst going up from `origin` and falling back down.
+    pub fn confetti(origin: Vec2) -> Self {
+        Self {
+            origin,
+            burst: 80,
+            lifetime: 2.5,
+            min_speed: 150.0,
+            max_speed: 350.0,
+            spread: PI / 2.0,
+            gravity: Vec2::new(0.0, 300.0),
+            size: 6.0,
+            colors: vec![
+                Color::srgb(0.94, 0.27, 0.27),
+                Color::srgb(0.98, 0.75, 0.14),
+                Color::srgb(0.13, 0.77, 0.37),
+                Color::srgb(0.23, 0.51, 0.96),
+            ],
+            ..default()
+        }
+    }
+
+    /// The default emitter node: covers the virtual area and clips particles to it.
+    fn node() -> Node {
+        Node {
+            width: Val::Percent(100.0),
+            height: Val::Percent(100.0),
+            position_type: PositionType::Absolute,
+            overflow: Overflow::clip(),
+            ..default()
+        }
+    }
+}
+
+/// Emission bookkeeping of a `UiParticleEmitter`.
+#[derive(Component, Default)]
+pub(crate) struct UiParticleEmitterState {
+    /// Fractional particles carried over between frames.
+    pending: f32,
+    /// Xorshift state, lazily seeded from the emitter.
+    rng: u32,
+}
+
+impl UiParticleEmitterState {
+    /// A uniform random value in `0.0..1.0`.
+    fn next(&mut self) -> f32 {
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 17;
+        self.rng ^= self.rng << 5;
+        (self.rng >> 8) as f32 / (1u32 << 24) as f32
+    }
+}
+
+/// A particle spawned by a `UiParticleEmitter`.
+#[derive(Component)]
+pub(crate) struct UiParticle {
+    position: Vec2,
+    velocity: Vec2,
+    gravity: Vec2,
+    size: f32,
+    age: f32,
+    lifetime: f32,
+    color: Color,
+}
+
+pub(crate) fn emit_ui_particles(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut emitters: Query<(Entity, &mut UiParticleEmitter, &mut UiParticleEmitterState)>,
+) {
+    for (entity, mut emitter, mut state) in emitters.iter_mut() {
+        if state.rng == 0 {
+            state.rng = emitter.seed.max(1);
+        }
+        state.pending += emitter.rate * time.delta_secs();
+        let count = emitter.burst + state.pending as u32;
+        state.pending = state.pending.fract();
+        if emitter.burst > 0 {
+            emitter.burst = 0;
+        }
+
+        for _ in 0..count {
+            let angle = emitter.direction + (state.next() - 0.5) * emitter.spread;
+            let speed = emitter.min_speed + state.next() * (emitter.max_speed - emitter.min_speed);
+            let color = match emitter.colors.len() {
+                0 => Color::WHITE,
+                len => emitter.colors[((state.next() * len as f32) as usize).min(len - 1)],
+            };
+            let particle = UiParticle {
+                position: emitter.origin,
+                velocity: Vec2::from_angle(angle) * speed,
+                gravity: emitter.gravity,
+                size: emitter.size,
+                age: 0.0,
+                lifetime: emitter.lifetime,
+                color,
+            };
+            commands.spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    left: Val::Px(particle.position.x - particle.size / 2.0),
+                    top: Val::Px(particle.position.y - particle.size / 2.0),
+                    width: Val::Px(particle.size),
+                    height: Val::Px(particle.size),
+                    ..default()
+                },
+                BackgroundColor(color),
+                Pickable::IGNORE,
+                particle,
+                ChildOf(entity),
+            ));
+        }
+    }
+}
+
+pub(crate) fn update_ui_particles(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut particles: Query<(Entity, &mut UiParticle, &mut Node, &mut BackgroundColor)>,
+) {
+    let delta = time.delta_secs();
+    for (entity, mut particle, mut node, mut background) in particles.iter_mut() {
+        particle.age += delta;
+        if particle.age >= particle.lifetime {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        let particle = &mut *particle;
+        particle.velocity += particle.gravity * delta;
+        particle.position += particle.velocity * delta;
+        node.left = Val::Px(particle.position.x - particle.size / 2.0);
+        node.top = Val::Px(particle.position.y - particle.size / 2.0);
+
+        let alpha = particle.color.alpha() * (1.0 - particle.age / particle.lifetime);
+        background.0 = particle.color.with_alpha(alpha);
+    }
+}
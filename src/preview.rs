@@ -0,0 +1,109 @@
+use bevy::prelude::*;
+
+use crate::{Hud, Letterbox, PREVIEW_FRAME_Z};
+
+/// Simulates a screen with another aspect ratio inside the current window.
+///
+/// While this resource is present the plugin letterboxes to the largest rect of the
+/// simulated aspect ratio that fits the window, then letterboxes that rect into the window
+/// again. Designers can check how 21:9 or 4:3 players will see the game without resizing the
+/// OS window. The simulated screen is framed with `frame_color` so its own bars stay
+/// distinguishable from the outer ones.
+///
+/// In window mode, cameras marked `AspectRatioCamera` render into the simulated screen as well.
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct AspectPreview {
+    aspect: f32,
+    /// The color of the simulated screen's frame.
+    pub frame_color: Color,
+}
+
+impl AspectPreview {
+    /// Simulates a screen of the given aspect ratio.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `aspect` is not a finite, positive number.
+    pub fn new(aspect: f32) -> Self {
+        Self {
+            aspect: validated(aspect),
+            frame_color: Color::srgb(0.9, 0.9, 0.9),
+        }
+    }
+
+    /// The simulated screen's width divided by its height, e.g. `21.0 / 9.0`.
+    pub fn aspect(&self) -> f32 {
+        self.aspect
+    }
+
+    /// Switches to simulating a screen of the given aspect ratio.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `aspect` is not a finite, positive number.
+    pub fn set_aspect(&mut self, aspect: f32) {
+        self.aspect = validated(aspect);
+    }
+
+    /// The largest size with the simulated aspect ratio that fits in `container_size`.
+    pub fn simulated_size(&self, container_size: Vec2) -> Vec2 {
+        if container_size.x / container_size.y > self.aspect {
+            Vec2::new(container_size.y * self.aspect, container_size.y)
+        } else {
+            Vec2::new(container_size.x, container_size.x / self.aspect)
+        }
+    }
+}
+
+fn validated(aspect: f32) -> f32 {
+    assert!(
+        aspect.is_finite() && aspect > 0.0,
+        "AspectPreview aspect must be finite and positive, got {aspect}"
+    );
+    aspect
+}
+
+/// The frame drawn around the simulated screen.
+#[derive(Component)]
+pub(crate) struct AspectPreviewFrame;
+
+/// The width of the simulated screen's frame, in virtual pixels.
+const FRAME_WIDTH: f32 = 2.0;
+
+/// Rebuilds the simulated screen's frame when the preview or the layout changes.
+pub(crate) fn update_aspect_preview_frame(
+    mut commands: Commands,
+    preview: Option<Res<AspectPreview>>,
+    letterbox: Res<Letterbox>,
+    hud: Res<Hud>,
+    frames: Query<Entity, With<AspectPreviewFrame>>,
+) {
+    for frame in frames.iter() {
+        commands.entity(frame).despawn();
+    }
+    let Some(preview) = preview else {
+        return;
+    };
+
+    // From container pixels to the HUD's virtual pixels.
+    let container = letterbox.container_size();
+    let simulated = preview.simulated_size(container) / letterbox.scale;
+    let position = (container / letterbox.scale - simulated) / 2.0 - letterbox.bars;
+    commands.spawn((
+        AspectPreviewFrame,
+        Name::new("Aspect Preview Frame"),
+        Node {
+            position_type: PositionType::Absolute,
+            left: Val::Px(position.x - FRAME_WIDTH),
+            top: Val::Px(position.y - FRAME_WIDTH),
+            width: Val::Px(simulated.x + 2.0 * FRAME_WIDTH),
+            height: Val::Px(simulated.y + 2.0 * FRAME_WIDTH),
+            border: UiRect::all(Val::Px(FRAME_WIDTH)),
+            ..default()
+        },
+        BorderColor::all(preview.frame_color),
+        GlobalZIndex(PREVIEW_FRAME_Z),
+        Pickable::IGNORE,
+        ChildOf(hud.0),
+    ));
+}
@@ -0,0 +1,42 @@
+use bevy::prelude::*;
+
+use crate::layout::LayoutSource;
+use crate::LetterboxChanged;
+
+/// Sent after every `LetterboxChanged` with the physical size of the virtual area.
+///
+/// Use it to scale rendering costs with the area actually being drawn, e.g. lowering MSAA,
+/// shadow resolution or particle counts when the game view shrinks to a small part of a
+/// high-DPI screen, and raising them again when it grows.
+#[derive(Message, Clone, Copy, Debug, PartialEq)]
+pub struct RenderQualityHint {
+    /// Physical pixels per virtual pixel.
+    pub scale: f32,
+    /// The size of the virtual area in physical pixels.
+    pub physical_size: Vec2,
+}
+
+impl RenderQualityHint {
+    /// The number of physical pixels covered by the virtual area.
+    pub fn pixel_count(&self) -> u32 {
+        (self.physical_size.x * self.physical_size.y).round() as u32
+    }
+}
+
+pub(crate) fn send_render_quality_hints(
+    source: LayoutSource,
+    mut letterbox_changed: MessageReader<LetterboxChanged>,
+    mut hints: MessageWriter<RenderQualityHint>,
+) {
+    let Some(changed) = letterbox_changed.read().last() else {
+        return;
+    };
+    let Some(physical_per_pixel) = source.physical_scale() else {
+        return;
+    };
+    let scale = changed.current.scale * physical_per_pixel;
+    hints.write(RenderQualityHint {
+        scale,
+        physical_size: changed.current.virtual_size() * scale,
+    });
+}
@@ -0,0 +1,212 @@
+use bevy::ecs::system::SystemParam;
+use bevy::input::mouse::MouseButtonInput;
+use bevy::input::touch::{TouchInput, TouchPhase};
+use bevy::input::ButtonState;
+use bevy::prelude::*;
+use bevy::window::{PrimaryWindow, WindowEvent};
+
+use crate::{Hud, ViewportCoords};
+
+/// A recorded pointer input, positioned in the HUD's virtual pixels.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum RecordedInput {
+    /// The cursor moved to a virtual position.
+    CursorMoved(Vec2),
+    /// A mouse button was pressed or released.
+    MouseButton(MouseButton, ButtonState),
+    /// A finger touched, moved on, or left the screen at a virtual position.
+    Touch {
+        /// The unique identifier of the finger.
+        id: u64,
+        /// The phase of the touch.
+        phase: TouchPhase,
+        /// The touch position in virtual pixels.
+        position: Vec2,
+    },
+}
+
+/// A sequence of pointer inputs in virtual coordinates, each with the time in seconds since
+/// the recording started.
+///
+/// Because positions are stored in virtual pixels, a recording made at one window size
+/// replays onto the same HUD elements at any other window size or aspect ratio. With the
+/// `serde` feature recordings can be saved, e.g. as test fixtures.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct InputRecording {
+    /// The recorded inputs, in order.
+    pub events: Vec<(f32, RecordedInput)>,
+}
+
+impl InputRecording {
+    /// The time of the last recorded input, in seconds.
+    pub fn duration(&self) -> f32 {
+        self.events.last().map_or(0.0, |(time, _)| *time)
+    }
+}
+
+/// Records cursor, mouse button and touch input of the primary window while present.
+///
+/// Insert it to start recording and remove it to stop, keeping its `recording`.
+///
+/// ```rust,no_run
+/// # use bevy::prelude::*;
+/// # use bevy_aspect_ratio_mask::{InputPlayback, InputRecorder};
+/// fn stop_and_replay(world: &mut World) {
+///     if let Some(recorder) = world.remove_resource::<InputRecorder>() {
+///         world.insert_resource(InputPlayback::new(recorder.recording));
+///     }
+/// }
+/// ```
+#[derive(Resource, Clone, Debug, Default)]
+pub struct InputRecorder {
+    /// The inputs recorded so far.
+    pub recording: InputRecording,
+    elapsed: f32,
+}
+
+/// Replays an `InputRecording` into the primary window while present, then removes itself.
+///
+/// Inputs are mapped from virtual pixels back to the current window and sent as regular
+/// window input, so `ButtonInput`, `Touches` and picking react as if a user were present.
+/// This makes UI tests and attract-mode demos deterministic across window sizes.
+#[derive(Resource, Clone, Debug)]
+pub struct InputPlayback {
+    recording: InputRecording,
+    elapsed: f32,
+    next: usize,
+    cursor: Option<Vec2>,
+}
+
+impl InputPlayback {
+    /// Starts replaying `recording` from its beginning.
+    pub fn new(recording: InputRecording) -> Self {
+        Self {
+            recording,
+            elapsed: 0.0,
+            next: 0,
+            cursor: None,
+        }
+    }
+
+    /// Whether every recorded input has been replayed.
+    pub fn is_finished(&self) -> bool {
+        self.next >= self.recording.events.len()
+    }
+}
+
+pub(crate) fn record_input(
+    time: Res<Time>,
+    hud: Res<Hud>,
+    coords: ViewportCoords,
+    mut recorder: ResMut<InputRecorder>,
+    mut cursor_moved: MessageReader<CursorMoved>,
+    mut mouse_buttons: MessageReader<MouseButtonInput>,
+    mut touches: MessageReader<TouchInput>,
+) {
+    let recorder = &mut *recorder;
+    recorder.elapsed += time.delta_secs();
+    let elapsed = recorder.elapsed;
+    let events = &mut recorder.recording.events;
+
+    for event in cursor_moved.read() {
+        if let Some(position) = coords.window_to_viewport(hud.0, event.position) {
+            events.push((elapsed, RecordedInput::CursorMoved(position)));
+        }
+    }
+    for event in mouse_buttons.read() {
+        events.push((
+            elapsed,
+            RecordedInput::MouseButton(event.button, event.state),
+        ));
+    }
+    for event in touches.read() {
+        if let Some(position) = coords.window_to_viewport(hud.0, event.position) {
+            events.push((
+                elapsed,
+                RecordedInput::Touch {
+                    id: event.id,
+                    phase: event.phase,
+                    position,
+                },
+            ));
+        }
+    }
+}
+
+/// The messages a window backend sends for pointer input.
+#[derive(SystemParam)]
+pub(crate) struct PointerInputWriters<'w> {
+    window_events: MessageWriter<'w, WindowEvent>,
+    cursor_moved: MessageWriter<'w, CursorMoved>,
+    mouse_buttons: MessageWriter<'w, MouseButtonInput>,
+    touches: MessageWriter<'w, TouchInput>,
+}
+
+pub(crate) fn play_input(
+    mut commands: Commands,
+    time: Res<Time>,
+    hud: Res<Hud>,
+    coords: ViewportCoords,
+    window: Single<Entity, With<PrimaryWindow>>,
+    mut playback: ResMut<InputPlayback>,
+    mut writers: PointerInputWriters,
+) {
+    let window = *window;
+    playback.elapsed += time.delta_secs();
+
+    while let Some((event_time, input)) = playback.recording.events.get(playback.next).copied() {
+        if event_time > playback.elapsed {
+            break;
+        }
+        playback.next += 1;
+
+        match input {
+            RecordedInput::CursorMoved(point) => {
+                let Some(position) = coords.viewport_to_window(hud.0, point) else {
+                    continue;
+                };
+                let event = CursorMoved {
+                    window,
+                    position,
+                    delta: playback.cursor.map(|cursor| position - cursor),
+                };
+                playback.cursor = Some(position);
+                writers.window_events.write(event.clone().into());
+                writers.cursor_moved.write(event);
+            }
+            RecordedInput::MouseButton(button, state) => {
+                let event = MouseButtonInput {
+                    button,
+                    state,
+                    window,
+                };
+                writers.window_events.write(event.into());
+                writers.mouse_buttons.write(event);
+            }
+            RecordedInput::Touch {
+                id,
+                phase,
+                position,
+            } => {
+                let Some(position) = coords.viewport_to_window(hud.0, position) else {
+                    continue;
+                };
+                let event = TouchInput {
+                    phase,
+                    position,
+                    window,
+                    force: None,
+                    id,
+                };
+                writers.window_events.write(event.into());
+                writers.touches.write(event);
+            }
+        }
+    }
+
+    if playback.is_finished() {
+        commands.remove_resource::<InputPlayback>();
+    }
+}
@@ -0,0 +1,235 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+use crate::Letterbox;
+
+/// Quantizes the UI scale to a fixed set of multiples for bitmap fonts.
+///
+/// Bitmap fonts only look sharp at the sizes they were baked for. With this mode
+/// enabled, the UI scale snaps down to the largest of `multiples` that still fits the
+/// window, and the active multiple is published in `ActiveScaleMultiple` so you can pick
+/// the matching pre-baked font.
+///
+/// Bars grow to absorb the space left over by the rounded-down scale. The scale never
+/// exceeds the fitting one, so the whole virtual area stays visible: in a window too small
+/// for the smallest multiple, the scale follows the window unquantized and
+/// `ActiveScaleMultiple` reports the smallest multiple, whose font is then drawn scaled down.
+#[derive(Resource, Clone, Debug, PartialEq)]
+pub struct BitmapFontScaling {
+    /// The supported scale multiples, e.g. `vec![1.0, 2.0, 3.0]`.
+    pub multiples: Vec<f32>,
+}
+
+impl Default for BitmapFontScaling {
+    fn default() -> Self {
+        Self {
+            multiples: vec![1.0, 2.0, 3.0, 4.0],
+        }
+    }
+}
+
+impl BitmapFontScaling {
+    /// Returns the UI scale for a window that fits the resolution at `fit_scale`: the largest
+    /// supported multiple up to `fit_scale`, or `fit_scale` itself if none fits.
+    pub fn quantize(&self, fit_scale: f32) -> f32 {
+        self.fitting(fit_scale).unwrap_or(fit_scale)
+    }
+
+    /// Returns the multiple whose font to use at `fit_scale`: the one `quantize` snaps to, or
+    /// the smallest one if none fits.
+    pub fn active_multiple(&self, fit_scale: f32) -> f32 {
+        self.fitting(fit_scale)
+            .or_else(|| self.multiples.iter().copied().reduce(f32::min))
+            .unwrap_or(fit_scale)
+    }
+
+    fn fitting(&self, fit_scale: f32) -> Option<f32> {
+        self.multiples
+            .iter()
+            .copied()
+            .filter(|multiple| *multiple <= fit_scale)
+            .reduce(f32::max)
+    }
+}
+
+/// The scale multiple currently selected by `BitmapFontScaling`.
+///
+/// Only present when the plugin is configured with `bitmap_font`.
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct ActiveScaleMultiple(pub f32);
+
+/// The `UiScale` the plugin wants, published instead of written in cooperative mode.
+///
+/// Set `AspectRatioPlugin::cooperative_ui_scale` (or insert this resource) when the app
+/// already manages `UiScale`, e.g. to combine this plugin with an accessibility text size and
+/// a debug zoom. The plugin then never writes `UiScale`: it keeps this resource up to date in
+/// window mode, and an app-level arbiter combines it with the other sources. Apply the result
+/// in `PostUpdate` before `UiSystems::Layout`, so it is in effect on the same frame.
+///
+/// The HUD and the bars are sized in UI pixels at this scale. When the arbiter applies a
+/// different one, e.g. multiplying it by a zoom factor, they are zoomed with the rest of the UI.
+///
+/// ```rust,no_run
+/// # use bevy::prelude::*;
+/// # use bevy_aspect_ratio_mask::DesiredUiScale;
+/// #[derive(Resource)]
+/// struct DebugZoom(f32);
+///
+/// fn arbitrate_ui_scale(
+///     desired: Res<DesiredUiScale>,
+///     zoom: Res<DebugZoom>,
+///     mut ui_scale: ResMut<UiScale>,
+/// ) {
+///     ui_scale.0 = desired.0 * zoom.0;
+/// }
+/// ```
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct DesiredUiScale(pub f32);
+
+impl Default for DesiredUiScale {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+/// How the UI scale is rounded, trading smooth resizing for crisper text.
+///
+/// Text and thin lines render sharpest when one virtual pixel covers a whole, or at least a
+/// simple fraction of, physical pixels. Rounding is applied to the physical scale (the fitting
+/// scale after `BitmapFontScaling`, if any, times the window's scale factor), always downwards
+/// so the whole virtual area stays visible, and the bars grow to absorb the space left over.
+/// When the scale is below one step, it is left unrounded. Changing this resource at runtime
+/// relayouts the window.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ScaleRounding {
+    /// The scale follows the window exactly.
+    #[default]
+    None,
+    /// Rounds down to a multiple of 1/4.
+    FloorQuarter,
+    /// Rounds down to a multiple of 1/8, a finer step than `FloorQuarter`.
+    FloorEighth,
+    /// Rounds down to a whole number, for pixel-exact UI.
+    Integer,
+}
+
+impl ScaleRounding {
+    /// Rounds the UI `scale` down so that one virtual pixel covers a rounded number of
+    /// physical pixels, at `physical_scale` physical pixels per logical pixel. Never exceeds
+    /// `scale`, which is returned as is when it's below one step.
+    pub fn round(self, scale: f32, physical_scale: f32) -> f32 {
+        let step = match self {
+            ScaleRounding::None => return scale,
+            ScaleRounding::FloorQuarter => 0.25,
+            ScaleRounding::FloorEighth => 0.125,
+            ScaleRounding::Integer => 1.0,
+        };
+        let rounded = (scale * physical_scale / step).floor() * step;
+        if rounded < step {
+            return scale;
+        }
+        rounded / physical_scale
+    }
+}
+
+/// Experimental: lets the HUD fill part of the bars by stretching its layout per axis.
+///
+/// When the window aspect doesn't match the resolution, the HUD root is sized wider (or
+/// taller) than the resolution, up to `max_distortion` times it, and the bars shrink to
+/// match. The UI scale stays uniform, so content isn't visually distorted; flexible and
+/// percentage-based layouts simply get more room. Fixed-size layouts should not rely on it.
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct AxisCompensation {
+    /// The largest HUD size relative to the resolution, e.g. `1.1` for 10%.
+    ///
+    /// The same limit applies to both axes; only the axis with bars is ever stretched.
+    pub max_distortion: f32,
+}
+
+impl Default for AxisCompensation {
+    fn default() -> Self {
+        Self {
+            max_distortion: 1.1,
+        }
+    }
+}
+
+/// The optional scale adjustments configured on the plugin.
+#[derive(SystemParam)]
+pub(crate) struct ScaleOptions<'w> {
+    bitmap_font: Option<Res<'w, BitmapFontScaling>>,
+    active_multiple: Option<ResMut<'w, ActiveScaleMultiple>>,
+    axis_compensation: Option<Res<'w, AxisCompensation>>,
+    rounding: Res<'w, ScaleRounding>,
+}
+
+impl ScaleOptions<'_> {
+    /// Adjusts the fitting scale, publishing the active bitmap-font multiple if enabled.
+    pub fn scale(&mut self, fit_scale: f32, physical_scale: f32) -> f32 {
+        let Some(bitmap_font) = &self.bitmap_font else {
+            return self.rounding.round(fit_scale, physical_scale);
+        };
+        let scale = bitmap_font.quantize(fit_scale);
+        if let Some(active_multiple) = &mut self.active_multiple {
+            active_multiple.set_if_neq(ActiveScaleMultiple(bitmap_font.active_multiple(fit_scale)));
+        }
+        self.rounding.round(scale, physical_scale)
+    }
+
+    /// Applies the layout adjustments to a computed letterbox.
+    pub fn adjust(&self, letterbox: Letterbox) -> Letterbox {
+        match &self.axis_compensation {
+            Some(axis_compensation) => letterbox.compensated(axis_compensation.max_distortion),
+            None => letterbox,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bitmap_font_snaps_down_to_a_fitting_multiple() {
+        let bitmap_font = BitmapFontScaling {
+            multiples: vec![1.0, 2.0, 3.0],
+        };
+        // Inside the range.
+        assert_eq!(bitmap_font.quantize(2.7), 2.0);
+        assert_eq!(bitmap_font.active_multiple(2.7), 2.0);
+        assert_eq!(bitmap_font.quantize(2.0), 2.0);
+        // Above it.
+        assert_eq!(bitmap_font.quantize(4.5), 3.0);
+        assert_eq!(bitmap_font.active_multiple(4.5), 3.0);
+        // Below it: the scale never exceeds the fit, the smallest font is drawn scaled down.
+        assert_eq!(bitmap_font.quantize(0.6), 0.6);
+        assert_eq!(bitmap_font.active_multiple(0.6), 1.0);
+    }
+
+    #[test]
+    fn rounding_never_exceeds_the_fit() {
+        // 1.2 is nearer to 1.25 than to 1.125, but rounding up would crop the virtual area.
+        assert_eq!(ScaleRounding::FloorEighth.round(1.2, 1.0), 1.125);
+        assert_eq!(ScaleRounding::FloorQuarter.round(1.2, 1.0), 1.0);
+        assert_eq!(ScaleRounding::Integer.round(1.8, 1.0), 1.0);
+        assert_eq!(ScaleRounding::None.round(1.2, 1.0), 1.2);
+        // Below one step, the fit is kept.
+        assert_eq!(ScaleRounding::Integer.round(0.8, 1.0), 0.8);
+        assert_eq!(ScaleRounding::FloorQuarter.round(0.2, 1.0), 0.2);
+    }
+
+    #[test]
+    fn rounding_applies_to_physical_pixels() {
+        // At a scale factor of 2, 0.8 is 1.6 physical pixels per virtual pixel: one whole one.
+        assert_eq!(ScaleRounding::Integer.round(0.8, 2.0), 0.5);
+        // At 2, 1.3 is 2.6 physical pixels, rounded down to 2.5.
+        assert_eq!(ScaleRounding::FloorQuarter.round(1.3, 2.0), 1.25);
+    }
+
+    #[test]
+    fn bitmap_font_without_multiples_follows_the_fit() {
+        let bitmap_font = BitmapFontScaling { multiples: vec![] };
+        assert_eq!(bitmap_font.quantize(1.3), 1.3);
+        assert_eq!(bitmap_font.active_multiple(1.3), 1.3);
+    }
+}
@@ -0,0 +1,76 @@
+use bevy::prelude::*;
+
+use crate::{FitPolicy, Letterbox, Resolution};
+
+/// A plain-data record of everything the plugin computed for the current layout.
+///
+/// External tools such as editors and test runners can store or exchange snapshots instead of
+/// depending on the plugin's internal types. The schema is versioned: fields are only ever
+/// added, in minor releases, and `version` is bumped whenever they are. Patch releases never
+/// change it. With the `serde` feature the snapshot serializes to a flat structure of numbers
+/// and strings, and fields missing from older snapshots deserialize to their defaults.
+///
+/// Vectors are `[x, y]` and rects `[min_x, min_y, max_x, max_y]`. Sizes are in virtual pixels
+/// unless noted otherwise.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+#[non_exhaustive]
+pub struct LayoutSnapshot {
+    /// The schema version, `LayoutSnapshot::VERSION` when captured by this release.
+    pub version: u32,
+    /// The virtual resolution.
+    pub resolution: [f32; 2],
+    /// How the world and HUD are fitted: `"letterbox"`, `"cover"` or `"expand"`.
+    pub fit_policy: String,
+    /// Container pixels per virtual pixel.
+    pub scale: f32,
+    /// The container (window or container node) size, in container pixels.
+    pub container_size: [f32; 2],
+    /// The container size.
+    pub viewport: [f32; 2],
+    /// The thickness of the left/right and top/bottom bars.
+    pub bars: [f32; 2],
+    /// The size of the virtual area.
+    pub virtual_size: [f32; 2],
+    /// The rect covered by the virtual area, in container pixels.
+    pub virtual_rect: [f32; 4],
+    /// The rect the world is drawn into, in container pixels.
+    pub world_rect: [f32; 4],
+}
+
+impl LayoutSnapshot {
+    /// The schema version written by this release.
+    pub const VERSION: u32 = 1;
+
+    /// Captures a layout computed for `resolution` under `policy`.
+    pub fn capture(letterbox: &Letterbox, resolution: &Resolution, policy: FitPolicy) -> Self {
+        let rect = |rect: Rect| [rect.min.x, rect.min.y, rect.max.x, rect.max.y];
+        Self {
+            version: Self::VERSION,
+            resolution: resolution.size().to_array(),
+            fit_policy: match policy {
+                FitPolicy::Letterbox => "letterbox",
+                FitPolicy::Cover => "cover",
+                FitPolicy::Expand => "expand",
+            }
+            .to_string(),
+            scale: letterbox.scale,
+            container_size: letterbox.container_size().to_array(),
+            viewport: letterbox.viewport.to_array(),
+            bars: letterbox.bars.to_array(),
+            virtual_size: letterbox.virtual_size().to_array(),
+            virtual_rect: rect(letterbox.virtual_rect()),
+            world_rect: rect(letterbox.world),
+        }
+    }
+
+    /// Captures the layout currently applied by the plugin.
+    pub fn from_world(world: &World) -> Option<Self> {
+        Some(Self::capture(
+            world.get_resource::<Letterbox>()?,
+            world.get_resource::<Resolution>()?,
+            *world.get_resource::<FitPolicy>()?,
+        ))
+    }
+}
@@ -0,0 +1,61 @@
+use bevy::prelude::*;
+
+use crate::{Letterbox, Resolution};
+
+/// Opt-in pixel-perfect sizing for a world sprite, matching the HUD's integer scale.
+///
+/// The sprite's `custom_size` is set so each texel covers exactly `n` × `n` window pixels,
+/// where `n` is the HUD scale rounded down to a whole number (at least 1). The size only
+/// steps when the scale crosses an integer, so world pixel art stays aligned with a HUD
+/// snapped by `BitmapFontScaling` instead of being resampled at fractional scales.
+///
+/// ```rust,no_run
+/// # use bevy::prelude::*;
+/// # use bevy_aspect_ratio_mask::PixelScaledSprite;
+/// fn spawn_player(mut commands: Commands, assets: Res<AssetServer>) {
+///     commands.spawn((
+///         Sprite::from_image(assets.load("player.png")),
+///         PixelScaledSprite::new(Vec2::new(16.0, 24.0)),
+///     ));
+/// }
+/// ```
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+#[require(Sprite)]
+pub struct PixelScaledSprite {
+    /// The sprite's size in texels.
+    pub size: Vec2,
+}
+
+impl PixelScaledSprite {
+    /// Pixel-perfect sizing for a sprite of `size` texels.
+    pub fn new(size: Vec2) -> Self {
+        Self { size }
+    }
+}
+
+/// The whole number of window pixels per texel for a HUD `scale`.
+fn pixel_multiple(scale: f32) -> f32 {
+    scale.floor().max(1.0)
+}
+
+pub(crate) fn update_pixel_scaled_sprites(
+    letterbox: Res<Letterbox>,
+    resolution: Res<Resolution>,
+    mut sprites: Query<(Ref<PixelScaledSprite>, &mut Sprite)>,
+) {
+    // Container pixels per world unit, as set up by the camera's scaling mode.
+    let world_scale = letterbox.world.width() / resolution.width;
+    if world_scale <= 0.0 {
+        return;
+    }
+    let multiple = pixel_multiple(letterbox.scale);
+    for (pixel_scaled, mut sprite) in sprites.iter_mut() {
+        if !letterbox.is_changed() && !pixel_scaled.is_changed() {
+            continue;
+        }
+        let size = Some(pixel_scaled.size * multiple / world_scale);
+        if sprite.custom_size != size {
+            sprite.custom_size = size;
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+
+/// Replaces the primary window as the source of the layout, for tests and benchmarks.
+///
+/// While this resource is present the plugin lays out for `size` and `scale_factor` instead
+/// of reading the primary window, so layouts can be driven deterministically in a headless
+/// `App` without an OS window, or even a `Window` entity. Changing or removing it triggers a
+/// relayout like a window resize. It has no effect in embedded mode (`AspectRatioContainer`),
+/// where the container node's size is used.
+///
+/// ```rust,no_run
+/// # use bevy::prelude::*;
+/// # use bevy_aspect_ratio_mask::{AspectRatioPlugin, Letterbox, SyntheticWindowSize};
+/// let mut app = App::new();
+/// app.add_plugins((MinimalPlugins, AspectRatioPlugin::default()))
+///     .init_resource::<UiScale>()
+///     .insert_resource(SyntheticWindowSize::new(1920.0, 1080.0));
+/// app.update();
+/// assert_eq!(app.world().resource::<Letterbox>().scale, 2.0);
+/// ```
+#[derive(Resource, Clone, Copy, Debug, PartialEq)]
+pub struct SyntheticWindowSize {
+    /// The window's logical size.
+    pub size: Vec2,
+    /// Physical pixels per logical pixel.
+    pub scale_factor: f32,
+}
+
+impl SyntheticWindowSize {
+    /// A window of `width` × `height` logical pixels with a scale factor of 1.
+    pub fn new(width: f32, height: f32) -> Self {
+        Self {
+            size: Vec2::new(width, height),
+            scale_factor: 1.0,
+        }
+    }
+
+    /// Sets the scale factor.
+    pub fn with_scale_factor(mut self, scale_factor: f32) -> Self {
+        self.scale_factor = scale_factor;
+        self
+    }
+}
+
+/// Reports when `SyntheticWindowSize` was inserted, changed or removed.
+#[derive(SystemParam)]
+pub(crate) struct SyntheticWindowChanged<'w, 's> {
+    size: Option<Res<'w, SyntheticWindowSize>>,
+    existed: Local<'s, bool>,
+}
+
+impl SyntheticWindowChanged<'_, '_> {
+    pub fn get(&mut self) -> bool {
+        let exists = self.size.is_some();
+        let removed = *self.existed && !exists;
+        *self.existed = exists;
+        removed || self.size.as_ref().is_some_and(|size| size.is_changed())
+    }
+}
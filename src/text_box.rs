@@ -0,0 +1,206 @@
+use bevy::prelude::*;
+use bevy::text::{load_font_to_fontdb, CosmicFontSystem, LineHeight, TextPipeline};
+use cosmic_text::{Attrs, Buffer, Family, Metrics, Shaping, Wrap};
+
+/// A dialogue box that wraps and paginates its text in virtual pixels.
+///
+/// Bevy wraps UI text at the physical size it is rendered at, so a line that just fits at one
+/// window size can break a word earlier at another. This box lays its text out once at the
+/// virtual resolution, from the box `size` and its `TextFont`, and shows the resulting lines
+/// without further wrapping: every device gets identical line breaks and page splits. Font
+/// size and line height are read from the entity's `TextFont` and `LineHeight`, in virtual
+/// pixels like the rest of the HUD.
+///
+/// Spawn it as a descendant of the HUD and advance it with `next_page`; the computed pages
+/// are available in `TextBoxPages`.
+///
+/// ```rust,no_run
+/// # use bevy::prelude::*;
+/// # use bevy_aspect_ratio_mask::{Hud, TextBox};
+/// fn talk(mut commands: Commands, hud: Res<Hud>) {
+///     commands.entity(hud.0).with_child((
+///         TextBox::new("It's dangerous to go alone! Take this.", Vec2::new(400.0, 96.0)),
+///         TextFont::from_font_size(24.0),
+///     ));
+/// }
+/// ```
+#[derive(Component, Clone, Debug, PartialEq)]
+#[require(
+    Node = TextBox::node(),
+    Text,
+    TextLayout = TextLayout::new_with_no_wrap(),
+    TextBoxPages
+)]
+pub struct TextBox {
+    /// The full text, split into pages by the plugin.
+    pub text: String,
+    /// The size of the box, in virtual pixels.
+    pub size: Vec2,
+    /// The page shown, clamped to the last page.
+    pub page: usize,
+}
+
+impl TextBox {
+    /// A box of `size` virtual pixels showing the first page of `text`.
+    pub fn new(text: impl Into<String>, size: Vec2) -> Self {
+        Self {
+            text: text.into(),
+            size,
+            page: 0,
+        }
+    }
+
+    /// Turns to the next page. Returns `false` if the last page was already shown.
+    pub fn next_page(&mut self, pages: &TextBoxPages) -> bool {
+        if self.page + 1 >= pages.len() {
+            return false;
+        }
+        self.page += 1;
+        true
+    }
+
+    /// The default box node: sized by `update_text_boxes` and clipping its text.
+    fn node() -> Node {
+        Node {
+            overflow: Overflow::clip(),
+            ..default()
+        }
+    }
+}
+
+/// The pages of a `TextBox`, each holding its already-broken lines.
+///
+/// Empty until the box's font is loaded.
+#[derive(Component, Clone, Debug, Default, PartialEq)]
+pub struct TextBoxPages {
+    pages: Vec<String>,
+}
+
+impl TextBoxPages {
+    /// The number of pages.
+    pub fn len(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// Whether the text hasn't been paginated yet.
+    pub fn is_empty(&self) -> bool {
+        self.pages.is_empty()
+    }
+
+    /// The text of page `index`, with lines separated by `\n`.
+    pub fn get(&self, index: usize) -> Option<&str> {
+        self.pages.get(index).map(String::as_str)
+    }
+}
+
+/// Paginates changed text boxes and shows their current page.
+#[allow(clippy::type_complexity)]
+pub(crate) fn update_text_boxes(
+    fonts: Res<Assets<Font>>,
+    mut pipeline: ResMut<TextPipeline>,
+    mut font_system: ResMut<CosmicFontSystem>,
+    mut boxes: Query<(
+        Ref<TextBox>,
+        Ref<TextFont>,
+        Option<Ref<LineHeight>>,
+        &mut TextBoxPages,
+        &mut Text,
+        &mut Node,
+    )>,
+) {
+    for (text_box, text_font, line_height, mut pages, mut text, mut node) in boxes.iter_mut() {
+        let settings_changed = text_box.is_changed()
+            || text_font.is_changed()
+            || line_height
+                .as_ref()
+                .is_some_and(|line_height| line_height.is_changed());
+        let needs_layout = pages.is_empty() && !text_box.text.is_empty();
+        if !settings_changed && !needs_layout {
+            continue;
+        }
+        // Retried every frame until the font is loaded.
+        if !fonts.contains(text_font.font.id()) || text_font.font_size <= 0.0 {
+            continue;
+        }
+
+        let line_height = line_height_px(
+            line_height.map_or(LineHeight::default(), |line_height| *line_height),
+            text_font.font_size,
+        );
+        let lines = break_lines(
+            &text_box,
+            &text_font,
+            line_height,
+            &fonts,
+            &mut pipeline,
+            &mut font_system,
+        );
+        let lines_per_page = ((text_box.size.y / line_height).floor() as usize).max(1);
+        let new_pages = TextBoxPages {
+            pages: lines
+                .chunks(lines_per_page)
+                .map(|page| page.join("\n"))
+                .collect(),
+        };
+        pages.set_if_neq(new_pages);
+
+        node.width = Val::Px(text_box.size.x);
+        node.height = Val::Px(text_box.size.y);
+        let page = pages
+            .get(text_box.page.min(pages.len().saturating_sub(1)))
+            .unwrap_or_default();
+        if text.0 != page {
+            text.0 = page.to_string();
+        }
+    }
+}
+
+/// Wraps the box's text at its virtual width, returning the text of each line.
+fn break_lines(
+    text_box: &TextBox,
+    text_font: &TextFont,
+    line_height: f32,
+    fonts: &Assets<Font>,
+    pipeline: &mut TextPipeline,
+    font_system: &mut CosmicFontSystem,
+) -> Vec<String> {
+    let face = load_font_to_fontdb(
+        text_font,
+        &mut font_system.0,
+        &mut pipeline.map_handle_to_font_id,
+        fonts,
+    );
+    let font_system = &mut font_system.0;
+    let metrics = Metrics::new(text_font.font_size, line_height);
+    let attrs = Attrs::new()
+        .family(Family::Name(&face.family_name))
+        .stretch(face.stretch)
+        .style(face.style)
+        .weight(text_font.weight.into());
+
+    let mut buffer = Buffer::new(font_system, metrics);
+    buffer.set_wrap(font_system, Wrap::WordOrGlyph);
+    buffer.set_size(font_system, Some(text_box.size.x), None);
+    buffer.set_text(font_system, &text_box.text, &attrs, Shaping::Advanced, None);
+    buffer.shape_until_scroll(font_system, false);
+
+    buffer
+        .layout_runs()
+        .map(|run| {
+            let start = run.glyphs.iter().map(|glyph| glyph.start).min();
+            let end = run.glyphs.iter().map(|glyph| glyph.end).max();
+            match start.zip(end) {
+                Some((start, end)) => run.text[start..end].trim_end().to_string(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/// The height of one line, in virtual pixels.
+fn line_height_px(line_height: LineHeight, font_size: f32) -> f32 {
+    match line_height {
+        LineHeight::Px(px) => px,
+        LineHeight::RelativeToFont(scale) => scale * font_size,
+    }
+}
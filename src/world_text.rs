@@ -0,0 +1,107 @@
+use bevy::prelude::*;
+use bevy::text::TextLayoutInfo;
+
+use crate::{AspectRatioCamera, Letterbox, Resolution};
+
+/// World-space text that matches the HUD's text size and stays inside the visible world.
+///
+/// A plain `Text2d` is rasterized at its font size and then scaled by the camera projection,
+/// so it doesn't line up with HUD text of the same size and gets blurry as the window grows.
+/// This helper rasterizes the text at the HUD's scale and counter-scales its `Transform`, so
+/// `font_size` virtual pixels look identical in the world and on the HUD. With
+/// `keep_visible`, the text is also moved as little as needed to stay inside the part of the
+/// world the `AspectRatioCamera` shows, bars excluded.
+///
+/// The plugin manages the entity's `Transform` and `TextFont::font_size`; set `position` to
+/// move the text. The text is kept in place around its center (the default `Anchor`).
+///
+/// ```rust,no_run
+/// # use bevy::prelude::*;
+/// # use bevy_aspect_ratio_mask::WorldText;
+/// fn label_enemy(mut commands: Commands) {
+///     commands.spawn((
+///         Text2d::new("Boss"),
+///         WorldText::new(16.0, Vec2::new(120.0, 80.0)),
+///     ));
+/// }
+/// ```
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+#[require(Text2d)]
+pub struct WorldText {
+    /// The font size, in virtual pixels.
+    pub font_size: f32,
+    /// Where the text's center is placed, in world units.
+    pub position: Vec2,
+    /// Moves the text back inside the visible world when it would be cut off.
+    pub keep_visible: bool,
+}
+
+impl WorldText {
+    /// World text of `font_size` virtual pixels centered at `position`, kept visible.
+    pub fn new(font_size: f32, position: Vec2) -> Self {
+        Self {
+            font_size,
+            position,
+            keep_visible: true,
+        }
+    }
+}
+
+pub(crate) fn update_world_text(
+    letterbox: Res<Letterbox>,
+    resolution: Res<Resolution>,
+    cameras: Query<&GlobalTransform, With<AspectRatioCamera>>,
+    mut texts: Query<(
+        &WorldText,
+        &mut TextFont,
+        &mut Transform,
+        Option<&TextLayoutInfo>,
+    )>,
+) {
+    // Container pixels per world unit, as set up by the camera's scaling mode.
+    let world_scale = letterbox.world.width() / resolution.width;
+    if world_scale <= 0.0 || letterbox.scale <= 0.0 {
+        return;
+    }
+
+    // The unmasked part of the world, relative to the camera, in world units (y up).
+    let visible = letterbox.world.intersect(Rect::from_corners(
+        letterbox.bars * letterbox.scale,
+        letterbox.container_size() - letterbox.bars * letterbox.scale,
+    ));
+    let offset = (visible.center() - letterbox.world.center()) / world_scale;
+    let camera = cameras
+        .iter()
+        .next()
+        .map_or(Vec2::ZERO, |camera| camera.translation().truncate());
+    let visible = Rect::from_center_size(
+        camera + Vec2::new(offset.x, -offset.y),
+        visible.size() / world_scale,
+    );
+
+    for (text, mut font, mut transform, layout) in texts.iter_mut() {
+        let font_size = text.font_size * letterbox.scale;
+        if font.font_size != font_size {
+            font.font_size = font_size;
+        }
+        let scale = Vec3::new(world_scale.recip(), world_scale.recip(), 1.0);
+
+        let mut position = text.position;
+        if let Some(layout) = layout.filter(|_| text.keep_visible) {
+            let half_size = layout.size / layout.scale_factor.max(f32::EPSILON) / world_scale / 2.0;
+            let min = visible.min + half_size;
+            let max = visible.max - half_size;
+            position = Vec2::select(
+                min.cmple(max),
+                position.clamp(min.min(max), max.max(min)),
+                visible.center(),
+            );
+        }
+
+        let translation = position.extend(transform.translation.z);
+        if transform.translation != translation || transform.scale != scale {
+            transform.translation = translation;
+            transform.scale = scale;
+        }
+    }
+}
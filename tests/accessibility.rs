@@ -0,0 +1,52 @@
+use accesskit::{Node as AccessKitNode, Rect, Role};
+use bevy::a11y::AccessibilityNode;
+use bevy::math::Affine2;
+use bevy::prelude::*;
+use bevy::ui::ComputedNode;
+use bevy::window::WindowResized;
+use bevy_aspect_ratio_mask::{AspectRatioPlugin, Hud};
+
+fn accessible_node(size: Vec2, transform: UiGlobalTransform) -> impl Bundle {
+    (
+        AccessibilityNode::from(AccessKitNode::new(Role::Button)),
+        ComputedNode { size, ..default() },
+        transform,
+    )
+}
+
+#[test]
+fn hud_bounds_include_the_transform_scale() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AspectRatioPlugin::default()))
+        .init_resource::<UiScale>()
+        .add_message::<WindowResized>();
+    app.update();
+
+    // A 100×40 button centered at (300, 200), drawn at half size by an enclosing transform.
+    let transform = UiGlobalTransform::from(
+        Affine2::from_translation(Vec2::new(300.0, 200.0)) * Affine2::from_scale(Vec2::splat(0.5)),
+    );
+    let hud = app.world().resource::<Hud>().0;
+    let button = app
+        .world_mut()
+        .spawn((
+            accessible_node(Vec2::new(100.0, 40.0), transform),
+            ChildOf(hud),
+        ))
+        .id();
+    let outside = app
+        .world_mut()
+        .spawn(accessible_node(Vec2::new(100.0, 40.0), transform))
+        .id();
+    app.update();
+
+    let bounds = |entity| {
+        app.world()
+            .get::<AccessibilityNode>(entity)
+            .unwrap()
+            .bounds()
+    };
+    assert_eq!(bounds(button), Some(Rect::new(275.0, 190.0, 325.0, 210.0)));
+    // UI outside the HUD is left to `bevy_ui`.
+    assert_eq!(bounds(outside), None);
+}
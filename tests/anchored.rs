@@ -0,0 +1,60 @@
+use bevy::ecs::system::RunSystemOnce;
+use bevy::prelude::*;
+use bevy::window::WindowResized;
+use bevy_aspect_ratio_mask::{AspectRatioPlugin, Hud, HudAnchor, HudSet};
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AspectRatioPlugin::default()))
+        .init_resource::<UiScale>()
+        .add_message::<WindowResized>();
+    app.update();
+    app
+}
+
+#[derive(Component)]
+struct Score;
+
+#[derive(Component)]
+struct Lives;
+
+#[test]
+fn hud_set_spawns_one_slot_per_anchor() {
+    let mut app = app();
+    let hud = app.world().resource::<Hud>().0;
+    let set = app
+        .world_mut()
+        .run_system_once(move |mut commands: Commands| {
+            HudSet::builder()
+                .margin(16.0)
+                .top_left(Score)
+                .top_left(Lives)
+                .bottom(Text::new("Press Space"))
+                .spawn(&mut commands, hud)
+        })
+        .unwrap();
+    app.update();
+
+    let world = app.world();
+    assert_eq!(world.get::<ChildOf>(set.root).unwrap().parent(), hud);
+    assert!(set.slot(HudAnchor::Center).is_none());
+
+    let top_left = set.slot(HudAnchor::TopLeft).unwrap();
+    let elements: Vec<_> = set.elements_at(HudAnchor::TopLeft).collect();
+    assert_eq!(elements.len(), 2);
+    assert!(world.get::<Score>(elements[0]).is_some());
+    assert!(world.get::<Lives>(elements[1]).is_some());
+    for element in elements {
+        assert_eq!(world.get::<ChildOf>(element).unwrap().parent(), top_left);
+    }
+    let slot = world.get::<Node>(top_left).unwrap();
+    assert_eq!(slot.padding, UiRect::all(Val::Px(16.0)));
+    assert_eq!(slot.justify_content, JustifyContent::FlexStart);
+    assert_eq!(slot.align_items, AlignItems::FlexStart);
+
+    app.world_mut()
+        .run_system_once(move |mut commands: Commands| set.set_margin(&mut commands, 4.0))
+        .unwrap();
+    let slot = app.world().get::<Node>(top_left).unwrap();
+    assert_eq!(slot.padding, UiRect::all(Val::Px(4.0)));
+}
@@ -0,0 +1,151 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use bevy::ui::{ComputedNode, UiGlobalTransform};
+use bevy::window::{PrimaryWindow, WindowResized, WindowResolution};
+use bevy_aspect_ratio_mask::{
+    AspectRatioContainer, AspectRatioPlugin, Hud, HudScaleAnimation, VirtualTween,
+};
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AspectRatioPlugin::default()))
+        .init_resource::<UiScale>()
+        .add_message::<WindowResized>()
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )));
+    app.update();
+    app
+}
+
+#[test]
+fn scale_animation_keeps_the_pivot_in_place() {
+    let mut app = app();
+    let hud = app.world().resource::<Hud>().0;
+    let menu = app
+        .world_mut()
+        .spawn((
+            Node::default(),
+            HudScaleAnimation::new(0.5, 2.0, 0.4)
+                .with_pivot(Vec2::new(100.0, 100.0))
+                .with_ease(EaseFunction::Linear),
+            ChildOf(hud),
+        ))
+        .id();
+    // Not laid out yet: the first frame is held.
+    app.update();
+    assert_eq!(
+        app.world().get::<UiTransform>(menu).unwrap().scale,
+        Vec2::splat(0.5)
+    );
+
+    // The menu is laid out centered on (400, 300) virtual pixels of the unscaled HUD.
+    app.world_mut().entity_mut(menu).insert((
+        ComputedNode {
+            size: Vec2::new(200.0, 100.0),
+            ..default()
+        },
+        UiGlobalTransform::from_translation(Vec2::new(400.0, 300.0)),
+    ));
+    for _ in 0..6 {
+        app.update();
+        let animation = *app.world().get::<HudScaleAnimation>(menu).unwrap();
+        let transform = app.world().get::<UiTransform>(menu).unwrap();
+        let scale = animation.scale();
+        // Scaling around its center moves the menu's pivot, which the translation undoes.
+        let translation = (Vec2::new(100.0, 100.0) - Vec2::new(400.0, 300.0)) * (1.0 - scale);
+        assert_eq!(transform.scale, Vec2::splat(scale));
+        assert_eq!(
+            transform.translation,
+            Val2::px(translation.x, translation.y)
+        );
+        // Lay the menu out again with its translation, as `UiSystems::Layout` would.
+        app.world_mut()
+            .entity_mut(menu)
+            .insert(UiGlobalTransform::from_translation(
+                Vec2::new(400.0, 300.0) + translation,
+            ));
+    }
+
+    let animation = app.world().get::<HudScaleAnimation>(menu).unwrap();
+    assert!(animation.is_finished());
+    let transform = app.world().get::<UiTransform>(menu).unwrap();
+    assert_eq!(transform.scale, Vec2::splat(2.0));
+    assert_eq!(transform.translation, Val2::px(300.0, 200.0));
+}
+
+/// Spawns a node with a finished tween to 100×40 virtual pixels right and up.
+fn spawn_tweened(app: &mut App, parent: Entity, inverse_scale_factor: f32) -> Entity {
+    app.world_mut()
+        .spawn((
+            Node::default(),
+            ComputedNode {
+                inverse_scale_factor,
+                ..default()
+            },
+            VirtualTween::new(Vec2::ZERO, Vec2::new(100.0, -40.0), 0.0),
+            ChildOf(parent),
+        ))
+        .id()
+}
+
+fn translation(app: &App, entity: Entity) -> Val2 {
+    app.world().get::<UiTransform>(entity).unwrap().translation
+}
+
+#[test]
+fn tween_offsets_by_virtual_pixels_in_window_mode() {
+    let mut app = app();
+    let window = app
+        .world_mut()
+        .spawn((
+            Window {
+                resolution: WindowResolution::new(1920, 1080),
+                ..default()
+            },
+            PrimaryWindow,
+        ))
+        .id();
+    app.world_mut().write_message(WindowResized {
+        window,
+        width: 1920.0,
+        height: 1080.0,
+    });
+    app.update();
+    // `UiScale` is 2 in window mode, so one UI pixel is one virtual pixel.
+    let hud = app.world().resource::<Hud>().0;
+    let node = spawn_tweened(&mut app, hud, 0.5);
+    app.update();
+
+    assert_eq!(translation(&app, node), Val2::px(100.0, -40.0));
+}
+
+#[test]
+fn tween_offsets_by_virtual_pixels_in_embedded_mode() {
+    let mut app = app();
+    let container = app
+        .world_mut()
+        .spawn((
+            Node::default(),
+            ComputedNode {
+                size: Vec2::new(1920.0, 1080.0),
+                ..default()
+            },
+        ))
+        .id();
+    app.insert_resource(AspectRatioContainer(container));
+    app.update();
+    // The HUD is scaled by its transform instead, and nodes outside it aren't scaled at all.
+    let hud = app.world().resource::<Hud>().0;
+    app.world_mut()
+        .entity_mut(hud)
+        .insert(UiGlobalTransform::from_scale(Vec2::splat(2.0)));
+    let inside = spawn_tweened(&mut app, hud, 1.0);
+    let outside = spawn_tweened(&mut app, container, 1.0);
+    app.update();
+
+    assert_eq!(translation(&app, inside), Val2::px(100.0, -40.0));
+    assert_eq!(translation(&app, outside), Val2::px(200.0, -80.0));
+}
@@ -0,0 +1,152 @@
+use bevy::prelude::*;
+use bevy::ui::{ComputedNode, UiGlobalTransform, UiStack};
+use bevy::window::{PrimaryWindow, WindowResized, WindowResolution};
+use bevy_aspect_ratio_mask::{AspectRatioPlugin, HitTestAudit, HitTestReport};
+
+#[derive(Resource, Default)]
+struct Reports(Vec<Vec2>);
+
+fn collect_reports(mut reports: MessageReader<HitTestReport>, mut sizes: ResMut<Reports>) {
+    sizes.0.extend(
+        reports
+            .read()
+            .map(|report| report.letterbox.container_size()),
+    );
+}
+
+#[derive(Resource, Default)]
+struct Unreachable(Vec<Entity>);
+
+fn collect_unreachable(
+    mut reports: MessageReader<HitTestReport>,
+    mut unreachable: ResMut<Unreachable>,
+) {
+    for report in reports.read() {
+        unreachable.0.extend(&report.unreachable);
+    }
+}
+
+/// Reports resizes like winit does for a real window.
+fn send_window_resized(
+    windows: Query<(Entity, &Window), Changed<Window>>,
+    mut resized: MessageWriter<WindowResized>,
+) {
+    for (window, state) in windows.iter() {
+        resized.write(WindowResized {
+            window,
+            width: state.width(),
+            height: state.height(),
+        });
+    }
+}
+
+fn app() -> (App, Entity) {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AspectRatioPlugin::default()))
+        .init_resource::<UiScale>()
+        .init_resource::<UiStack>()
+        .init_resource::<Reports>()
+        .add_message::<WindowResized>()
+        .add_systems(Last, collect_reports);
+    let window = app
+        .world_mut()
+        .spawn((
+            Window {
+                resolution: WindowResolution::new(1920, 1080),
+                ..default()
+            },
+            PrimaryWindow,
+        ))
+        .id();
+    app.update();
+    (app, window)
+}
+
+fn run_audit(app: &mut App) {
+    for _ in 0..200 {
+        if !app.world().contains_resource::<HitTestAudit>() {
+            return;
+        }
+        app.update();
+    }
+    panic!("the audit never finished");
+}
+
+#[test]
+fn audit_sweeps_window_sizes_and_restores_the_window() {
+    let (mut app, window) = app();
+    app.add_systems(First, send_window_resized);
+    app.insert_resource(
+        HitTestAudit::new(16.0)
+            .with_window_sizes([Vec2::new(800.0, 1200.0), Vec2::new(2560.0, 720.0)]),
+    );
+    run_audit(&mut app);
+
+    assert_eq!(
+        app.world().resource::<Reports>().0,
+        vec![Vec2::new(800.0, 1200.0), Vec2::new(2560.0, 720.0)]
+    );
+    app.update();
+    let window = app.world().get::<Window>(window).unwrap();
+    assert_eq!(window.resolution.size(), Vec2::new(1920.0, 1080.0));
+}
+
+#[test]
+fn audit_moves_on_when_the_layout_never_follows_a_resize() {
+    // The window never reports the resize, as when the OS refuses it.
+    let (mut app, _) = app();
+    app.insert_resource(HitTestAudit::new(16.0).with_window_sizes([Vec2::new(800.0, 1200.0)]));
+    run_audit(&mut app);
+
+    assert_eq!(
+        app.world().resource::<Reports>().0,
+        vec![Vec2::new(1920.0, 1080.0)]
+    );
+}
+
+#[cfg(feature = "testing")]
+#[test]
+fn audit_ignores_window_sizes_when_the_window_doesnt_drive_the_layout() {
+    use bevy_aspect_ratio_mask::SyntheticWindowSize;
+
+    let (mut app, window) = app();
+    app.insert_resource(SyntheticWindowSize::new(1280.0, 720.0));
+    app.update();
+    app.insert_resource(HitTestAudit::new(16.0).with_window_sizes([Vec2::new(800.0, 1200.0)]));
+    run_audit(&mut app);
+
+    assert_eq!(
+        app.world().resource::<Reports>().0,
+        vec![Vec2::new(1280.0, 720.0)]
+    );
+    let window = app.world().get::<Window>(window).unwrap();
+    assert_eq!(window.resolution.size(), Vec2::new(1920.0, 1080.0));
+}
+
+#[test]
+fn audit_skips_stacked_entities_that_arent_ui_nodes() {
+    let (mut app, _) = app();
+    app.init_resource::<Unreachable>()
+        .add_systems(Last, collect_unreachable);
+    let stray = app.world_mut().spawn_empty().id();
+    let button = app
+        .world_mut()
+        .spawn((
+            Node::default(),
+            Interaction::None,
+            ComputedNode {
+                size: Vec2::new(1920.0, 1080.0),
+                ..default()
+            },
+            UiGlobalTransform::from_translation(Vec2::new(960.0, 540.0)),
+            InheritedVisibility::VISIBLE,
+        ))
+        .id();
+    // The entity without UI components comes before the button in the stack.
+    app.world_mut().resource_mut::<UiStack>().uinodes = vec![stray, button];
+    app.insert_resource(HitTestAudit::new(16.0));
+    run_audit(&mut app);
+
+    assert_eq!(app.world().resource::<Reports>().0.len(), 1);
+    assert!(app.world().resource::<Unreachable>().0.is_empty());
+}
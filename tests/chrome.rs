@@ -0,0 +1,50 @@
+use bevy::prelude::*;
+use bevy::window::{PrimaryWindow, WindowResized, WindowResolution};
+use bevy_aspect_ratio_mask::{
+    AspectRatioMaskSide, AspectRatioPlugin, MaskRegions, WindowControl, WindowControls,
+};
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AspectRatioPlugin::default()))
+        .init_resource::<UiScale>()
+        .add_message::<WindowResized>();
+    // 16:10, so the top and bottom bars are shown.
+    app.world_mut().spawn((
+        Window {
+            resolution: WindowResolution::new(1920, 1200),
+            ..default()
+        },
+        PrimaryWindow,
+    ));
+    app.update();
+    app
+}
+
+fn controls(app: &mut App) -> Vec<(Entity, WindowControl)> {
+    app.world_mut()
+        .query::<(Entity, &WindowControl)>()
+        .iter(app.world())
+        .map(|(entity, control)| (entity, *control))
+        .collect()
+}
+
+#[test]
+fn window_controls_are_spawned_in_their_bar() {
+    let mut app = app();
+    app.insert_resource(WindowControls {
+        side: AspectRatioMaskSide::Bottom,
+        ..default()
+    });
+    app.update();
+
+    let buttons = controls(&mut app);
+    assert_eq!(buttons.len(), 3);
+    let bottom = app.world().resource::<MaskRegions>().bottom;
+    let row = app.world().get::<ChildOf>(buttons[0].0).unwrap().parent();
+    assert_eq!(app.world().get::<ChildOf>(row).unwrap().parent(), bottom);
+
+    app.world_mut().remove_resource::<WindowControls>();
+    app.update();
+    assert!(controls(&mut app).is_empty());
+}
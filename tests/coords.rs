@@ -0,0 +1,71 @@
+use bevy::ecs::system::RunSystemOnce;
+use bevy::math::Affine2;
+use bevy::prelude::*;
+use bevy::ui::{ComputedNode, UiGlobalTransform};
+use bevy::window::{PrimaryWindow, WindowResolution};
+use bevy_aspect_ratio_mask::{CoordinateChain, ViewportCoords};
+
+fn viewport(size: Vec2, transform: Affine2) -> (ComputedNode, UiGlobalTransform) {
+    (
+        ComputedNode {
+            size,
+            inverse_scale_factor: 0.5,
+            ..default()
+        },
+        UiGlobalTransform::from(transform),
+    )
+}
+
+#[test]
+fn chain_maps_through_nested_viewports_and_back() {
+    let mut world = World::new();
+    world.spawn((
+        Window {
+            resolution: WindowResolution::new(1920, 1080).with_scale_factor_override(2.0),
+            ..default()
+        },
+        PrimaryWindow,
+    ));
+    // An outer viewport of 400×200 virtual pixels centered in the window, and an inner one of
+    // 100×50 drawn at twice its size, slightly off the window's center.
+    let outer = world
+        .spawn(viewport(
+            Vec2::new(800.0, 400.0),
+            Affine2::from_translation(Vec2::new(960.0, 540.0)),
+        ))
+        .id();
+    let inner = world
+        .spawn(viewport(
+            Vec2::new(200.0, 100.0),
+            Affine2::from_translation(Vec2::new(1000.0, 500.0))
+                * Affine2::from_scale(Vec2::splat(2.0)),
+        ))
+        .id();
+
+    let chain = world
+        .run_system_once(move |coords: ViewportCoords| coords.chain(&[outer, inner]))
+        .unwrap()
+        .expect("both viewports can be mapped");
+    assert_eq!(chain.len(), 3);
+
+    // The window's center, in logical pixels.
+    let center = Vec2::new(480.0, 270.0);
+    assert_eq!(chain.map(center, 0, 1), Vec2::new(200.0, 100.0));
+    assert_eq!(chain.from_window(center), Vec2::new(40.0, 35.0));
+    // Between the nested spaces, in both directions.
+    assert_eq!(
+        chain.map(Vec2::new(40.0, 35.0), 2, 1),
+        Vec2::new(200.0, 100.0)
+    );
+    assert_eq!(
+        chain.map(Vec2::new(200.0, 100.0), 1, 2),
+        Vec2::new(40.0, 35.0)
+    );
+    assert!(chain.to_window(chain.from_window(center)).distance(center) < 1e-4);
+
+    // Links compose in order: swapping them maps to other points.
+    let swapped = CoordinateChain::window()
+        .then(chain.between(0, 2))
+        .then(chain.between(0, 1));
+    assert_ne!(swapped.between(1, 2), chain.between(1, 2));
+}
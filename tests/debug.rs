@@ -0,0 +1,150 @@
+use bevy::prelude::*;
+use bevy::ui::{ComputedNode, UiGlobalTransform};
+use bevy::window::{PrimaryWindow, WindowResized, WindowResolution};
+use bevy_aspect_ratio_mask::{
+    AspectRatioPlugin, DebugMeasurement, DebugOverlay, DebugPalette, Hud,
+};
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AspectRatioPlugin::default()))
+        .init_resource::<UiScale>()
+        .add_message::<WindowResized>();
+    // A 16:10 window: the 16:9 virtual area has bars above and below it.
+    app.world_mut().spawn((
+        Window {
+            resolution: WindowResolution::new(1920, 1200),
+            ..default()
+        },
+        PrimaryWindow,
+    ));
+    app.update();
+    app
+}
+
+/// The outlined regions of the overlay with their color and hatching stripe count.
+fn outlines(app: &mut App) -> Vec<(Color, usize)> {
+    app.world_mut()
+        .query::<(&BorderColor, Option<&Children>)>()
+        .iter(app.world())
+        .filter(|(border, _)| border.top.alpha() > 0.0)
+        .map(|(border, children)| (border.top, children.map_or(0, |children| children.len())))
+        .collect()
+}
+
+#[test]
+fn palettes_keep_regions_distinct() {
+    for palette in [
+        DebugPalette::Standard,
+        DebugPalette::OkabeIto,
+        DebugPalette::TolBright,
+        DebugPalette::Monochrome,
+    ] {
+        let colors = palette.colors();
+        assert_ne!(colors.grid, colors.safe_area, "{palette:?}");
+        assert_ne!(colors.grid, colors.bars, "{palette:?}");
+        assert_ne!(colors.safe_area, colors.bars, "{palette:?}");
+    }
+}
+
+#[test]
+fn overlay_draws_the_regions_in_the_palette_colors() {
+    let mut app = app();
+    app.insert_resource(DebugOverlay {
+        palette: DebugPalette::OkabeIto,
+        ..default()
+    });
+    app.update();
+
+    let colors = DebugPalette::OkabeIto.colors();
+    let mut regions = outlines(&mut app);
+    regions.sort_by_key(|(color, _)| *color == colors.bars);
+    assert_eq!(regions.len(), 3);
+    assert_eq!(regions[0], (colors.safe_area, 0));
+    // Both bars are hatched in their color.
+    for (color, stripes) in &regions[1..] {
+        assert_eq!(*color, colors.bars);
+        assert!(*stripes > 0);
+    }
+
+    app.world_mut().resource_mut::<DebugOverlay>().hatching = false;
+    app.update();
+    assert!(outlines(&mut app).iter().all(|(_, stripes)| *stripes == 0));
+}
+
+fn texts(app: &mut App) -> Vec<String> {
+    app.world_mut()
+        .query::<&Text>()
+        .iter(app.world())
+        .map(|text| text.0.clone())
+        .collect()
+}
+
+#[test]
+fn rulers_label_every_64_virtual_pixels_without_a_grid() {
+    let mut app = app();
+    app.insert_resource(DebugOverlay {
+        grid_spacing: 0.0,
+        ..default()
+    });
+    app.update();
+
+    // 15 labels along the 960 pixel width, the last at its edge, and 8 along the height.
+    let labels = texts(&mut app);
+    assert_eq!(labels.len(), 23);
+    assert_eq!(labels.iter().filter(|label| *label == "64").count(), 2);
+    assert_eq!(labels.iter().filter(|label| *label == "960").count(), 1);
+
+    app.world_mut().resource_mut::<DebugOverlay>().rulers = false;
+    app.update();
+    assert!(texts(&mut app).is_empty());
+}
+
+fn move_cursor(app: &mut App, position: Vec2) {
+    app.world_mut()
+        .query_filtered::<&mut Window, With<PrimaryWindow>>()
+        .single_mut(app.world_mut())
+        .unwrap()
+        .set_cursor_position(Some(position));
+}
+
+fn buttons(app: &mut App) -> Mut<'_, ButtonInput<MouseButton>> {
+    app.world_mut().resource_mut::<ButtonInput<MouseButton>>()
+}
+
+#[test]
+fn dragging_measures_in_virtual_pixels() {
+    let mut app = app();
+    app.init_resource::<ButtonInput<MouseButton>>()
+        .insert_resource(DebugOverlay {
+            rulers: false,
+            ..default()
+        });
+    // The HUD is laid out over the 16:9 middle of the window, at twice the resolution.
+    let hud = app.world().resource::<Hud>().0;
+    app.world_mut().entity_mut(hud).insert((
+        ComputedNode {
+            size: Vec2::new(1920.0, 1080.0),
+            inverse_scale_factor: 0.5,
+            ..default()
+        },
+        UiGlobalTransform::from_translation(Vec2::new(960.0, 600.0)),
+    ));
+    move_cursor(&mut app, Vec2::new(200.0, 160.0));
+    buttons(&mut app).press(MouseButton::Right);
+    app.update();
+    buttons(&mut app).clear();
+    move_cursor(&mut app, Vec2::new(500.0, 560.0));
+    app.update();
+    buttons(&mut app).release(MouseButton::Right);
+    app.update();
+    // Moving after the release doesn't change the measurement.
+    move_cursor(&mut app, Vec2::new(0.0, 0.0));
+    app.update();
+
+    let measurement = *app.world().resource::<DebugMeasurement>();
+    assert_eq!(measurement.start, Vec2::new(100.0, 50.0));
+    assert_eq!(measurement.end, Vec2::new(250.0, 250.0));
+    assert_eq!(measurement.distance(), 250.0);
+    assert_eq!(texts(&mut app), vec!["150 × 200 (250.0 px)".to_string()]);
+}
@@ -0,0 +1,37 @@
+use bevy::prelude::*;
+use bevy::window::{PrimaryWindow, WindowResized, WindowResolution};
+use bevy_aspect_ratio_mask::{AspectRatioPlugin, EffectiveDpi, ScreenPpi};
+
+fn app(width: u32, height: u32) -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AspectRatioPlugin::default()))
+        .init_resource::<UiScale>()
+        .add_message::<WindowResized>();
+    app.world_mut().spawn((
+        Window {
+            resolution: WindowResolution::new(width, height).with_scale_factor_override(2.0),
+            ..default()
+        },
+        PrimaryWindow,
+    ));
+    app.update();
+    app
+}
+
+#[test]
+fn effective_dpi_follows_the_scale_and_scale_factor() {
+    // 960×540 logical pixels at a scale factor of 2: one virtual pixel is two physical ones.
+    let mut app = app(1920, 1080);
+    let dpi = *app.world().resource::<EffectiveDpi>();
+    assert_eq!(dpi.physical_per_virtual, 2.0);
+    assert_eq!(dpi.screen_ppi, 192.0);
+    assert!(!dpi.measured);
+    assert_eq!(dpi.virtual_ppi(), 96.0);
+    assert_eq!(dpi.mm_for_virtual_px(96.0), 25.4);
+
+    app.insert_resource(ScreenPpi(400.0));
+    app.update();
+    let dpi = *app.world().resource::<EffectiveDpi>();
+    assert_eq!(dpi.screen_ppi, 400.0);
+    assert!(dpi.measured);
+}
@@ -0,0 +1,36 @@
+#![cfg(feature = "editor")]
+
+use bevy::prelude::*;
+use bevy::window::{PrimaryWindow, WindowResized, WindowResolution};
+use bevy_aspect_ratio_mask::{AspectRatioPlugin, EditorBridge, EditorCommand};
+
+#[test]
+fn preview_resets_and_restores_the_ui_scale() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AspectRatioPlugin::default()))
+        .init_resource::<UiScale>()
+        .add_message::<WindowResized>();
+    app.world_mut().spawn((
+        Window {
+            resolution: WindowResolution::new(1920, 1080),
+            ..default()
+        },
+        PrimaryWindow,
+    ));
+    let (bridge, handle) = EditorBridge::new();
+    app.insert_resource(bridge);
+    app.update();
+    assert_eq!(app.world().resource::<UiScale>().0, 2.0);
+
+    // The preview is sized in logical window pixels, so the window-mode scale must not apply.
+    handle
+        .commands
+        .send(EditorCommand::ResizePreview(Vec2::new(800.0, 450.0)))
+        .unwrap();
+    app.update();
+    assert_eq!(app.world().resource::<UiScale>().0, 1.0);
+
+    handle.commands.send(EditorCommand::ResetPreview).unwrap();
+    app.update();
+    assert_eq!(app.world().resource::<UiScale>().0, 2.0);
+}
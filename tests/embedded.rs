@@ -0,0 +1,36 @@
+use bevy::prelude::*;
+use bevy::ui::ComputedNode;
+use bevy::window::WindowResized;
+use bevy_aspect_ratio_mask::{AspectRatioContainer, AspectRatioPlugin, Hud};
+
+#[test]
+fn hud_follows_the_container_on_the_frame_it_changes() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AspectRatioPlugin::default()))
+        .init_resource::<UiScale>()
+        .add_message::<WindowResized>();
+    let container = app
+        .world_mut()
+        .spawn((
+            Node::default(),
+            ComputedNode {
+                size: Vec2::new(1920.0, 1080.0),
+                ..default()
+            },
+        ))
+        .id();
+    app.insert_resource(AspectRatioContainer(container));
+    app.update();
+
+    let hud = app.world().resource::<Hud>().0;
+    let scale = |app: &App| app.world().get::<UiTransform>(hud).unwrap().scale;
+    assert_eq!(scale(&app), Vec2::splat(2.0));
+
+    // The container is resized, e.g. by an editor panel: the HUD is rescaled the same frame.
+    app.world_mut()
+        .get_mut::<ComputedNode>(container)
+        .unwrap()
+        .size = Vec2::new(1440.0, 810.0);
+    app.update();
+    assert_eq!(scale(&app), Vec2::splat(1.5));
+}
@@ -0,0 +1,125 @@
+use bevy::prelude::*;
+use bevy::window::{PrimaryWindow, WindowResized, WindowResolution};
+use bevy_aspect_ratio_mask::{
+    AspectRatioPlugin, ExtremeAspectFallback, ExtremeAspectRatio, OrientationLock,
+    ScreenOrientation,
+};
+
+fn app(plugin: AspectRatioPlugin, width: u32, height: u32) -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, plugin))
+        .init_resource::<UiScale>()
+        .add_message::<WindowResized>();
+    app.world_mut().spawn((
+        Window {
+            resolution: WindowResolution::new(width, height),
+            ..default()
+        },
+        PrimaryWindow,
+    ));
+    app.update();
+    app
+}
+
+fn z_index_of(app: &mut App, name: &str) -> Option<i32> {
+    app.world_mut()
+        .query::<(&Name, &GlobalZIndex)>()
+        .iter(app.world())
+        .find(|(entity_name, _)| entity_name.as_str() == name)
+        .map(|(_, z_index)| z_index.0)
+}
+
+#[test]
+fn rotate_prompt_stacks_above_the_resize_cover() {
+    // A 5:1 landscape window: too wide, and in the wrong orientation.
+    let mut app = app(
+        AspectRatioPlugin {
+            extreme_aspect: Some(ExtremeAspectRatio {
+                fallback: ExtremeAspectFallback::ResizeCover,
+                ..default()
+            }),
+            orientation_lock: Some(OrientationLock::new(ScreenOrientation::Portrait)),
+            ..default()
+        },
+        1000,
+        200,
+    );
+    app.update();
+
+    let prompt = z_index_of(&mut app, "Rotate Prompt").expect("the rotate prompt is shown");
+    let cover = z_index_of(&mut app, "Resize Cover").expect("the resize cover is shown");
+    assert!(prompt > cover);
+}
+
+#[test]
+fn clamp_lays_out_for_the_nearest_supported_aspect() {
+    use bevy_aspect_ratio_mask::Letterbox;
+
+    // 32:9 is clamped to 3:1, so the bars grow instead of the HUD drifting apart.
+    let mut app = app(
+        AspectRatioPlugin {
+            extreme_aspect: Some(ExtremeAspectRatio::default()),
+            ..default()
+        },
+        3840,
+        1080,
+    );
+    let letterbox = *app.world().resource::<Letterbox>();
+    assert_eq!(letterbox.container_size(), Vec2::new(3840.0, 1080.0));
+    assert_eq!(letterbox.scale, 2.0);
+    assert_eq!(letterbox.virtual_size(), Vec2::new(960.0, 540.0));
+    assert_eq!(
+        app.world()
+            .resource::<bevy_aspect_ratio_mask::ActiveExtremeFallback>()
+            .0,
+        Some(ExtremeAspectFallback::Clamp)
+    );
+    assert!(z_index_of(&mut app, "Resize Cover").is_none());
+}
+
+#[test]
+fn simplified_hud_swaps_the_variants() {
+    use bevy_aspect_ratio_mask::HudVariant;
+
+    let mut app = app(
+        AspectRatioPlugin {
+            extreme_aspect: Some(ExtremeAspectRatio {
+                fallback: ExtremeAspectFallback::SimplifiedHud,
+                ..default()
+            }),
+            ..default()
+        },
+        3840,
+        1080,
+    );
+    let full = app.world_mut().spawn(HudVariant::Full).id();
+    let simplified = app.world_mut().spawn(HudVariant::Simplified).id();
+    app.update();
+
+    assert_eq!(
+        app.world().get::<Visibility>(full),
+        Some(&Visibility::Hidden)
+    );
+    assert_eq!(
+        app.world().get::<Visibility>(simplified),
+        Some(&Visibility::Inherited)
+    );
+}
+
+#[test]
+fn rotate_prompt_follows_the_orientation() {
+    let mut app = app(
+        AspectRatioPlugin {
+            orientation_lock: Some(OrientationLock::new(ScreenOrientation::Landscape)),
+            ..default()
+        },
+        1920,
+        1080,
+    );
+    assert!(z_index_of(&mut app, "Rotate Prompt").is_none());
+
+    app.world_mut()
+        .insert_resource(OrientationLock::new(ScreenOrientation::Portrait));
+    app.update();
+    assert!(z_index_of(&mut app, "Rotate Prompt").is_some());
+}
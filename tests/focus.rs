@@ -0,0 +1,66 @@
+use bevy::input_focus::InputFocus;
+use bevy::prelude::*;
+use bevy::ui::ComputedNode;
+use bevy::window::WindowResized;
+use bevy_aspect_ratio_mask::{AspectRatioPlugin, FocusOutline};
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AspectRatioPlugin::default()))
+        .init_resource::<UiScale>()
+        .add_message::<WindowResized>()
+        .insert_resource(FocusOutline {
+            always_visible: true,
+            ..default()
+        });
+    app
+}
+
+fn focus(app: &mut App, element: Entity) -> Outline {
+    app.world_mut().insert_resource(InputFocus(Some(element)));
+    app.update();
+    *app.world().get::<Outline>(element).unwrap()
+}
+
+#[test]
+fn outline_is_snapped_to_physical_pixels() {
+    let mut app = app();
+    // A 2x display: the default 2 virtual pixel outline is 4 physical pixels.
+    let element = app
+        .world_mut()
+        .spawn((
+            Node::default(),
+            ComputedNode {
+                inverse_scale_factor: 0.5,
+                ..default()
+            },
+        ))
+        .id();
+
+    let outline = focus(&mut app, element);
+    assert_eq!(
+        (outline.width, outline.offset),
+        (Val::Px(2.0), Val::Px(2.0))
+    );
+}
+
+#[test]
+fn outline_accounts_for_transform_scaled_huds() {
+    let mut app = app();
+    // In embedded mode the HUD is scaled by its transform, not by `UiScale`: at a HUD scale of
+    // 1.3 on a 2x display, 2 virtual pixels are 5.2 physical pixels, drawn 5 pixels thick.
+    let element = app
+        .world_mut()
+        .spawn((
+            Node::default(),
+            ComputedNode {
+                inverse_scale_factor: 0.5,
+                ..default()
+            },
+            UiGlobalTransform::from_scale(Vec2::splat(1.3)),
+        ))
+        .id();
+
+    let outline = focus(&mut app, element);
+    assert_eq!(outline.width, Val::Px(5.0 / 2.6));
+}
@@ -0,0 +1,52 @@
+use bevy::prelude::*;
+use bevy::window::{PrimaryWindow, WindowResized, WindowResolution};
+use bevy_aspect_ratio_mask::{AspectRatioMaskSide, AspectRatioPlugin, FitPolicy, HudGutters};
+
+#[test]
+fn gutters_follow_the_expanded_world() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AspectRatioPlugin {
+            policy: FitPolicy::Expand,
+            hud_gutters: true,
+            ..default()
+        },
+    ))
+    .init_resource::<UiScale>()
+    .add_message::<WindowResized>();
+    // 21:9: 150 virtual pixels of extra world on the left and right.
+    app.world_mut().spawn((
+        Window {
+            resolution: WindowResolution::new(2520, 1080),
+            ..default()
+        },
+        PrimaryWindow,
+    ));
+    app.update();
+
+    let gutters = *app.world().resource::<HudGutters>();
+    let left = app
+        .world()
+        .get::<Node>(gutters.get(AspectRatioMaskSide::Left))
+        .unwrap();
+    assert_eq!(
+        (left.left, left.top, left.width, left.height),
+        (
+            Val::Px(-150.0),
+            Val::Px(0.0),
+            Val::Px(150.0),
+            Val::Px(540.0)
+        )
+    );
+    let right = app
+        .world()
+        .get::<Node>(gutters.get(AspectRatioMaskSide::Right))
+        .unwrap();
+    assert_eq!(right.left, Val::Px(960.0));
+    let top = app
+        .world()
+        .get::<Node>(gutters.get(AspectRatioMaskSide::Top))
+        .unwrap();
+    assert_eq!(top.height, Val::Px(0.0));
+}
@@ -0,0 +1,48 @@
+use bevy::prelude::*;
+use bevy::window::{PrimaryWindow, WindowResized, WindowResolution};
+use bevy_aspect_ratio_mask::{AspectRatioPlugin, HitTargetInflation, Hud};
+
+fn app(width: u32, height: u32) -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AspectRatioPlugin::default()))
+        .init_resource::<UiScale>()
+        .add_message::<WindowResized>();
+    app.world_mut().spawn((
+        Window {
+            resolution: WindowResolution::new(width, height).with_scale_factor_override(2.0),
+            ..default()
+        },
+        PrimaryWindow,
+    ));
+    app.update();
+    app
+}
+
+#[test]
+fn hit_areas_grow_when_the_hud_is_drawn_small() {
+    // 240×135 logical pixels at a scale factor of 2: a virtual pixel is half a physical one.
+    let mut app = app(480, 270);
+    app.insert_resource(HitTargetInflation {
+        min_scale: 1.0,
+        max_inflation: 1.5,
+    });
+    let hud = app.world().resource::<Hud>().0;
+    let button = app.world_mut().spawn((Button, ChildOf(hud))).id();
+    let outside = app.world_mut().spawn(Button).id();
+    app.update();
+    app.update();
+
+    let area = app
+        .world()
+        .get::<Children>(button)
+        .and_then(|children| children.first().copied())
+        .expect("the button gets a hit area");
+    let node = app.world().get::<Node>(area).unwrap();
+    assert_eq!(node.width, Val::Percent(150.0));
+    assert_eq!(node.left, Val::Percent(-25.0));
+    assert!(app.world().get::<Children>(outside).is_none());
+
+    app.world_mut().remove_resource::<HitTargetInflation>();
+    app.update();
+    assert!(app.world().get_entity(area).is_err());
+}
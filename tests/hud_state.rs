@@ -0,0 +1,104 @@
+use bevy::prelude::*;
+use bevy::window::WindowResized;
+use bevy_aspect_ratio_mask::{
+    AspectRatioPlugin, HudElementState, HudScaleAnimation, HudState, HudStateKey,
+};
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AspectRatioPlugin::default()))
+        .init_resource::<UiScale>()
+        .add_message::<WindowResized>();
+    app.update();
+    app
+}
+
+fn element(app: &mut App, key: &str) -> Entity {
+    app.world_mut()
+        .query::<(Entity, &HudStateKey)>()
+        .iter(app.world())
+        .find(|(_, element_key)| element_key.0 == key)
+        .map(|(entity, _)| entity)
+        .unwrap()
+}
+
+#[test]
+fn restore_applies_to_a_respawned_element() {
+    let mut app = app();
+    let transform = UiTransform::from_translation(Val2::px(12.0, 34.0));
+    let spawned = app
+        .world_mut()
+        .spawn((HudStateKey::new("minimap"), Node::default(), transform))
+        .id();
+    app.update();
+
+    let state = HudState::capture(app.world_mut());
+    app.world_mut().despawn(spawned);
+    state.restore(app.world_mut());
+    app.update();
+
+    // Respawned as in its spawn code, with an animation the captured element didn't have.
+    app.world_mut().spawn((
+        HudStateKey::new("minimap"),
+        Node::default(),
+        HudScaleAnimation::new(0.5, 1.0, 0.2),
+    ));
+    app.update();
+
+    let respawned = element(&mut app, "minimap");
+    let world = app.world();
+    assert_eq!(world.get::<UiTransform>(respawned), Some(&transform));
+    assert!(world.get::<HudScaleAnimation>(respawned).is_none());
+}
+
+#[test]
+fn pending_state_expires() {
+    let mut app = app();
+    let mut state = HudState::default();
+    state.insert(
+        "minimap",
+        HudElementState {
+            visibility: Some(Visibility::Hidden),
+            ..default()
+        },
+    );
+    state.restore(app.world_mut());
+    for _ in 0..=HudState::PENDING_TIMEOUT_FRAMES {
+        app.update();
+    }
+
+    app.world_mut()
+        .spawn((HudStateKey::new("minimap"), Node::default()));
+    app.update();
+
+    let spawned = element(&mut app, "minimap");
+    assert_eq!(
+        app.world().get::<Visibility>(spawned),
+        Some(&Visibility::Inherited)
+    );
+}
+
+#[test]
+fn pending_state_can_be_cleared() {
+    let mut app = app();
+    let mut state = HudState::default();
+    state.insert(
+        "minimap",
+        HudElementState {
+            visibility: Some(Visibility::Hidden),
+            ..default()
+        },
+    );
+    state.restore(app.world_mut());
+    HudState::clear_pending(app.world_mut());
+
+    app.world_mut()
+        .spawn((HudStateKey::new("minimap"), Node::default()));
+    app.update();
+
+    let spawned = element(&mut app, "minimap");
+    assert_eq!(
+        app.world().get::<Visibility>(spawned),
+        Some(&Visibility::Inherited)
+    );
+}
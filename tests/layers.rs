@@ -0,0 +1,105 @@
+use bevy::input_focus::tab_navigation::TabIndex;
+use bevy::input_focus::InputFocus;
+use bevy::prelude::*;
+use bevy::window::{PrimaryWindow, WindowResized, WindowResolution};
+use bevy_aspect_ratio_mask::{
+    AspectRatioMask, AspectRatioMaskSide, AspectRatioPlugin, Hud, HudLayer, MaskLayer, MaskRegions,
+};
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AspectRatioPlugin {
+            mask: AspectRatioMask {
+                layer: MaskLayer::AboveHud,
+                ..default()
+            },
+            ..default()
+        },
+    ))
+    .init_resource::<UiScale>()
+    .init_resource::<InputFocus>()
+    .add_message::<WindowResized>();
+    // A 16:10 window: the 16:9 virtual area has bars above and below it.
+    app.world_mut().spawn((
+        Window {
+            resolution: WindowResolution::new(1920, 1200),
+            ..default()
+        },
+        PrimaryWindow,
+    ));
+    app.update();
+    app
+}
+
+/// Spawns a shown modal layer on the HUD.
+fn spawn_modal(app: &mut App) -> Entity {
+    let hud = app.world().resource::<Hud>().0;
+    app.world_mut()
+        .spawn((
+            HudLayer::modal(),
+            InheritedVisibility::VISIBLE,
+            ChildOf(hud),
+        ))
+        .id()
+}
+
+/// The picking nodes drawn over the bars, other than the bars themselves.
+fn bar_shields(app: &mut App) -> Vec<Node> {
+    let root = app.world().resource::<MaskRegions>().root;
+    let children = app.world().get::<Children>(root).unwrap().to_vec();
+    children
+        .into_iter()
+        .filter(|child| {
+            !app.world().entity(*child).contains::<AspectRatioMaskSide>()
+                && app.world().entity(*child).contains::<Pickable>()
+        })
+        .map(|child| app.world().get::<Node>(child).unwrap().clone())
+        .collect()
+}
+
+#[test]
+fn capturing_layer_shields_bars_drawn_above_the_hud() {
+    let mut app = app();
+    let modal = spawn_modal(&mut app);
+    app.update();
+
+    let regions = *app.world().resource::<MaskRegions>();
+    let top = app.world().get::<Node>(regions.top).unwrap().clone();
+    let shields = bar_shields(&mut app);
+    assert_eq!(shields.len(), 4);
+    assert!(shields
+        .iter()
+        .any(|shield| shield.height == top.height && shield.display == Display::Flex));
+
+    // A hidden layer lets input through again, and a removed one takes its shields along.
+    app.world_mut().get_mut::<Node>(modal).unwrap().display = Display::None;
+    app.update();
+    assert!(bar_shields(&mut app)
+        .iter()
+        .all(|shield| shield.display == Display::None));
+    app.world_mut().entity_mut(modal).despawn();
+    app.update();
+    assert!(bar_shields(&mut app).is_empty());
+}
+
+#[test]
+fn capturing_layer_moves_focus_into_its_tab_group() {
+    let mut app = app();
+    let hud = app.world().resource::<Hud>().0;
+    let outside = app.world_mut().spawn((TabIndex(0), ChildOf(hud))).id();
+    let modal = spawn_modal(&mut app);
+    let first = app.world_mut().spawn((TabIndex(0), ChildOf(modal))).id();
+    app.world_mut().spawn((TabIndex(1), ChildOf(modal)));
+    app.world_mut().insert_resource(InputFocus(Some(outside)));
+    app.update();
+
+    assert_eq!(app.world().resource::<InputFocus>().0, Some(first));
+
+    // Focus inside the layer is left alone.
+    let second = app.world().get::<Children>(modal).unwrap()[1];
+    app.world_mut().insert_resource(InputFocus(Some(second)));
+    app.update();
+    assert_eq!(app.world().resource::<InputFocus>().0, Some(second));
+}
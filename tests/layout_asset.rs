@@ -0,0 +1,109 @@
+#![cfg(feature = "layout_asset")]
+
+use bevy::prelude::*;
+use bevy::window::WindowResized;
+use bevy_aspect_ratio_mask::{
+    AspectRatioPlugin, Hud, HudLayout, HudLayoutAsset, HudLayoutElement, HudLayoutMigration,
+};
+
+#[test]
+fn loader_is_registered_when_the_asset_plugin_comes_later() {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AspectRatioPlugin::default(),
+        AssetPlugin::default(),
+    ));
+    app.finish();
+    app.cleanup();
+
+    assert!(app.world().contains_resource::<Assets<HudLayoutAsset>>());
+}
+
+#[test]
+fn loader_is_skipped_without_the_asset_plugin() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AspectRatioPlugin::default()));
+    app.finish();
+    app.cleanup();
+
+    assert!(!app.world().contains_resource::<Assets<HudLayoutAsset>>());
+}
+
+fn rename_hp(version: u32, mut layout: HudLayoutAsset) -> HudLayoutAsset {
+    if version < 2 {
+        for element in &mut layout.elements {
+            if element.name == "hp" {
+                element.name = "health".into();
+            }
+        }
+    }
+    layout
+}
+
+#[test]
+fn older_layouts_are_migrated_and_spawned() {
+    let assets = std::env::temp_dir().join(format!("hud_layout_{}", std::process::id()));
+    std::fs::create_dir_all(&assets).unwrap();
+    std::fs::write(
+        assets.join("old.hud.ron"),
+        "(version: 1, elements: [(name: \"hp\", anchor: TopLeft, text: Some(\"100\"))])",
+    )
+    .unwrap();
+
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin {
+            file_path: assets.to_string_lossy().into_owned(),
+            ..default()
+        },
+        AspectRatioPlugin {
+            layout_migration: Some(HudLayoutMigration {
+                version: 2,
+                migrate: rename_hp,
+            }),
+            ..default()
+        },
+    ))
+    .init_resource::<UiScale>()
+    .add_message::<WindowResized>();
+    app.finish();
+    app.cleanup();
+    app.update();
+
+    let handle: Handle<HudLayoutAsset> = app.world().resource::<AssetServer>().load("old.hud.ron");
+    let hud = app.world().resource::<Hud>().0;
+    app.world_mut()
+        .spawn((HudLayout(handle.clone()), ChildOf(hud)));
+    for _ in 0..500 {
+        app.update();
+        if app
+            .world()
+            .resource::<Assets<HudLayoutAsset>>()
+            .contains(&handle)
+        {
+            break;
+        }
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+    app.update();
+    std::fs::remove_dir_all(&assets).ok();
+
+    let layout = app
+        .world()
+        .resource::<Assets<HudLayoutAsset>>()
+        .get(&handle)
+        .expect("the layout loads")
+        .clone();
+    assert_eq!(layout.version, 2);
+    assert_eq!(layout.elements[0].name, "health");
+
+    let names: Vec<String> = app
+        .world_mut()
+        .query::<&HudLayoutElement>()
+        .iter(app.world())
+        .map(|element| element.0.clone())
+        .collect();
+    assert_eq!(names, ["health"]);
+}
@@ -0,0 +1,54 @@
+use bevy::prelude::*;
+use bevy::ui::OverrideClip;
+use bevy::window::WindowResized;
+use bevy_aspect_ratio_mask::{AspectRatioPlugin, ExtendIntoBars};
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AspectRatioPlugin::default()))
+        .init_resource::<UiScale>()
+        .add_message::<WindowResized>();
+    app
+}
+
+#[test]
+fn extend_into_bars_inserts_and_removes_its_components() {
+    let mut app = app();
+    let node = app
+        .world_mut()
+        .spawn((Node::default(), ExtendIntoBars))
+        .id();
+    app.update();
+
+    let entity = app.world().entity(node);
+    assert!(entity.contains::<OverrideClip>());
+    assert_eq!(entity.get::<GlobalZIndex>(), Some(&GlobalZIndex(1)));
+
+    app.world_mut().entity_mut(node).remove::<ExtendIntoBars>();
+    app.update();
+
+    let entity = app.world().entity(node);
+    assert!(!entity.contains::<OverrideClip>());
+    assert!(!entity.contains::<GlobalZIndex>());
+}
+
+#[test]
+fn extend_into_bars_keeps_user_components() {
+    let mut app = app();
+    let node = app
+        .world_mut()
+        .spawn((Node::default(), GlobalZIndex(5), ExtendIntoBars))
+        .id();
+    app.update();
+
+    let entity = app.world().entity(node);
+    assert!(entity.contains::<OverrideClip>());
+    assert_eq!(entity.get::<GlobalZIndex>(), Some(&GlobalZIndex(5)));
+
+    app.world_mut().entity_mut(node).remove::<ExtendIntoBars>();
+    app.update();
+
+    let entity = app.world().entity(node);
+    assert!(!entity.contains::<OverrideClip>());
+    assert_eq!(entity.get::<GlobalZIndex>(), Some(&GlobalZIndex(5)));
+}
@@ -0,0 +1,43 @@
+use bevy::prelude::*;
+use bevy::ui::ComputedNode;
+use bevy::window::WindowResized;
+use bevy_aspect_ratio_mask::{AspectRatioPlugin, NestedLetterbox, NestedViewport, Resolution};
+
+#[test]
+fn nested_viewport_letterboxes_into_its_parent() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AspectRatioPlugin::default()))
+        .init_resource::<UiScale>()
+        .add_message::<WindowResized>();
+    let parent = app
+        .world_mut()
+        .spawn((
+            Node::default(),
+            ComputedNode {
+                size: Vec2::new(600.0, 224.0),
+                ..default()
+            },
+        ))
+        .id();
+    let viewport = app
+        .world_mut()
+        .spawn((
+            NestedViewport::new(Resolution {
+                width: 256.0,
+                height: 224.0,
+            }),
+            ChildOf(parent),
+        ))
+        .id();
+    app.update();
+
+    let letterbox = app.world().get::<NestedLetterbox>(viewport).unwrap();
+    assert_eq!(letterbox.scale, 1.0);
+    assert_eq!(letterbox.bars, Vec2::new(172.0, 0.0));
+    let node = app.world().get::<Node>(viewport).unwrap();
+    assert_eq!(node.left, Val::Px(172.0));
+    assert_eq!(node.width, Val::Px(256.0));
+
+    // Four bars hang off the viewport.
+    assert_eq!(app.world().get::<Children>(viewport).unwrap().len(), 4);
+}
@@ -0,0 +1,68 @@
+use bevy::prelude::*;
+use bevy::ui::ComputedNode;
+use bevy::window::WindowResized;
+use bevy_aspect_ratio_mask::{AspectRatioPlugin, NineSlicePanel};
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AspectRatioPlugin::default()))
+        .init_resource::<UiScale>()
+        .add_message::<WindowResized>();
+    app
+}
+
+fn max_corner_scale(app: &mut App, panel: Entity) -> f32 {
+    match app.world().get::<ImageNode>(panel).unwrap().image_mode {
+        NodeImageMode::Sliced(ref slicer) => slicer.max_corner_scale,
+        ref mode => panic!("expected a sliced image, got {mode:?}"),
+    }
+}
+
+#[test]
+fn border_is_snapped_to_physical_pixels() {
+    let mut app = app();
+    // A 2x display: 6 virtual pixels are 12 physical pixels, 1.5 times the 8 pixel slice.
+    let panel = app
+        .world_mut()
+        .spawn((
+            NineSlicePanel::new(Handle::default(), 8.0, 6.0),
+            ComputedNode {
+                inverse_scale_factor: 0.5,
+                ..default()
+            },
+        ))
+        .id();
+    app.update();
+
+    assert_eq!(max_corner_scale(&mut app, panel), 1.5);
+}
+
+#[test]
+fn border_accounts_for_transform_scaled_huds() {
+    let mut app = app();
+    // In embedded mode the HUD is scaled by its transform, not by `UiScale`: at a HUD scale
+    // of 1.5 on a 2x display, 6 virtual pixels are 18 physical pixels, which the transform
+    // scales from 12 pixels in layout space.
+    let panel = app
+        .world_mut()
+        .spawn((
+            NineSlicePanel::new(Handle::default(), 8.0, 6.0),
+            ComputedNode {
+                inverse_scale_factor: 0.5,
+                ..default()
+            },
+            UiGlobalTransform::from_scale(Vec2::splat(1.5)),
+        ))
+        .id();
+    app.update();
+
+    assert_eq!(max_corner_scale(&mut app, panel), 1.5);
+
+    // A fractional physical thickness is rounded: 6 * 1.3 * 2 = 15.6, drawn 16 pixels thick.
+    app.world_mut()
+        .entity_mut(panel)
+        .insert(UiGlobalTransform::from_scale(Vec2::splat(1.3)));
+    app.update();
+
+    assert_eq!(max_corner_scale(&mut app, panel), 16.0 / 1.3 / 8.0);
+}
@@ -0,0 +1,51 @@
+use bevy::prelude::*;
+use bevy::window::WindowResized;
+use bevy_aspect_ratio_mask::{AspectRatioPlugin, NestedViewport, Resolution, StreamOverlay};
+
+#[test]
+fn overlay_targets_its_camera_and_follows_changes() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AspectRatioPlugin::default()))
+        .init_resource::<UiScale>()
+        .add_message::<WindowResized>();
+    let stream_camera = app.world_mut().spawn_empty().id();
+    let overlay = app
+        .world_mut()
+        .spawn(StreamOverlay::new(
+            stream_camera,
+            Resolution {
+                width: 1920.0,
+                height: 1080.0,
+            },
+        ))
+        .id();
+    app.update();
+
+    // The overlay is a nested viewport under a root that only the stream camera renders.
+    let root = app.world().get::<ChildOf>(overlay).unwrap().parent();
+    assert_eq!(
+        app.world().get::<UiTargetCamera>(root).unwrap().entity(),
+        stream_camera
+    );
+    let viewport = app.world().get::<NestedViewport>(overlay).unwrap();
+    assert_eq!(viewport.resolution.width, 1920.0);
+
+    let other_camera = app.world_mut().spawn_empty().id();
+    {
+        let mut settings = app.world_mut().get_mut::<StreamOverlay>(overlay).unwrap();
+        settings.camera = other_camera;
+        settings.resolution.height = 720.0;
+    }
+    app.update();
+    assert_eq!(
+        app.world().get::<UiTargetCamera>(root).unwrap().entity(),
+        other_camera
+    );
+    let viewport = app.world().get::<NestedViewport>(overlay).unwrap();
+    assert_eq!(viewport.resolution.height, 720.0);
+
+    // Despawning the overlay takes its root with it.
+    app.world_mut().entity_mut(overlay).despawn();
+    app.update();
+    assert!(app.world().get_entity(root).is_err());
+}
@@ -0,0 +1,62 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use bevy::window::WindowResized;
+use bevy_aspect_ratio_mask::{AspectRatioPlugin, Hud, UiParticleEmitter};
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AspectRatioPlugin::default()))
+        .init_resource::<UiScale>()
+        .add_message::<WindowResized>()
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            500,
+        )));
+    app.update();
+    app
+}
+
+fn particles(app: &App, emitter: Entity) -> Vec<(Val, Val, Color)> {
+    let Some(children) = app.world().get::<Children>(emitter) else {
+        return Vec::new();
+    };
+    children
+        .iter()
+        .map(|particle| {
+            let node = app.world().get::<Node>(particle).unwrap();
+            let color = app.world().get::<BackgroundColor>(particle).unwrap().0;
+            (node.left, node.top, color)
+        })
+        .collect()
+}
+
+#[test]
+fn bursts_are_seeded_and_expire() {
+    let mut app = app();
+    let hud = app.world().resource::<Hud>().0;
+    let origin = Vec2::new(480.0, 200.0);
+    let first = app
+        .world_mut()
+        .spawn((UiParticleEmitter::confetti(origin), ChildOf(hud)))
+        .id();
+    let second = app
+        .world_mut()
+        .spawn((UiParticleEmitter::confetti(origin), ChildOf(hud)))
+        .id();
+    app.update();
+
+    // The whole burst is emitted at once, in the same pattern for the same seed.
+    let burst = particles(&app, first);
+    assert_eq!(burst.len(), 80);
+    assert_eq!(burst, particles(&app, second));
+    app.update();
+    assert_eq!(particles(&app, first).len(), 80);
+    assert_ne!(particles(&app, first), burst);
+
+    // Confetti lives 2.5 seconds, and isn't emitted again.
+    for _ in 0..10 {
+        app.update();
+    }
+    assert!(particles(&app, first).is_empty());
+}
@@ -0,0 +1,114 @@
+#![cfg(feature = "camera")]
+
+use bevy::prelude::*;
+use bevy::window::{PrimaryWindow, WindowResized, WindowResolution};
+use bevy_aspect_ratio_mask::{AspectPreview, AspectRatioCamera, AspectRatioPlugin};
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AspectRatioPlugin::default()))
+        .init_resource::<UiScale>()
+        .add_message::<WindowResized>();
+    app.world_mut().spawn((
+        Window {
+            resolution: WindowResolution::new(1920, 1080),
+            ..default()
+        },
+        PrimaryWindow,
+    ));
+    app.update();
+    app
+}
+
+#[test]
+fn preview_restricts_the_camera_viewport_to_the_simulated_screen() {
+    let mut app = app();
+    let camera = app
+        .world_mut()
+        .spawn((Camera::default(), AspectRatioCamera))
+        .id();
+
+    // 4:3 inside 16:9: a 1440x1080 screen in the middle of the window.
+    app.world_mut()
+        .insert_resource(AspectPreview::new(4.0 / 3.0));
+    app.update();
+    let viewport = app
+        .world()
+        .get::<Camera>(camera)
+        .unwrap()
+        .viewport
+        .clone()
+        .expect("the preview sets a viewport");
+    assert_eq!(viewport.physical_position, UVec2::new(240, 0));
+    assert_eq!(viewport.physical_size, UVec2::new(1440, 1080));
+
+    app.world_mut().remove_resource::<AspectPreview>();
+    app.update();
+    assert!(app
+        .world()
+        .get::<Camera>(camera)
+        .unwrap()
+        .viewport
+        .is_none());
+}
+
+#[test]
+fn preview_leaves_a_user_viewport_alone_when_inactive() {
+    let mut app = app();
+    let viewport = bevy::camera::Viewport {
+        physical_size: UVec2::new(100, 100),
+        ..default()
+    };
+    let camera = app
+        .world_mut()
+        .spawn((
+            Camera {
+                viewport: Some(viewport.clone()),
+                ..default()
+            },
+            AspectRatioCamera,
+        ))
+        .id();
+    app.update();
+
+    let current = app.world().get::<Camera>(camera).unwrap().viewport.clone();
+    assert_eq!(
+        current.map(|viewport| viewport.physical_size),
+        Some(viewport.physical_size)
+    );
+}
+
+#[test]
+#[should_panic]
+fn preview_rejects_a_degenerate_aspect() {
+    AspectPreview::new(0.0);
+}
+
+#[test]
+#[should_panic]
+fn preview_rejects_setting_a_degenerate_aspect() {
+    AspectPreview::new(4.0 / 3.0).set_aspect(f32::NAN);
+}
+
+#[test]
+fn preview_follows_a_changed_aspect() {
+    let mut app = app();
+    let camera = app
+        .world_mut()
+        .spawn((Camera::default(), AspectRatioCamera))
+        .id();
+    app.world_mut()
+        .insert_resource(AspectPreview::new(4.0 / 3.0));
+    app.update();
+
+    // 1:1 inside 16:9: a 1080x1080 screen in the middle of the window.
+    app.world_mut()
+        .resource_mut::<AspectPreview>()
+        .set_aspect(1.0);
+    app.update();
+    let viewport = app.world().get::<Camera>(camera).unwrap().viewport.clone();
+    assert_eq!(
+        viewport.map(|viewport| viewport.physical_size),
+        Some(UVec2::new(1080, 1080))
+    );
+}
@@ -0,0 +1,58 @@
+use bevy::prelude::*;
+use bevy::window::{PrimaryWindow, WindowResized, WindowResolution};
+use bevy_aspect_ratio_mask::{AspectRatioPlugin, RenderQualityHint};
+
+#[derive(Resource, Default)]
+struct Hints(Vec<RenderQualityHint>);
+
+fn collect_hints(mut hints: MessageReader<RenderQualityHint>, mut collected: ResMut<Hints>) {
+    collected.0.extend(hints.read().copied());
+}
+
+#[test]
+fn hints_report_the_physical_size_of_the_virtual_area() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AspectRatioPlugin::default()))
+        .init_resource::<UiScale>()
+        .init_resource::<Hints>()
+        .add_message::<WindowResized>()
+        .add_systems(Last, collect_hints);
+    // 1080×1080 logical pixels at a scale factor of 2: the 960×540 area is drawn at 1.125.
+    let window = app
+        .world_mut()
+        .spawn((
+            Window {
+                resolution: WindowResolution::new(2160, 2160).with_scale_factor_override(2.0),
+                ..default()
+            },
+            PrimaryWindow,
+        ))
+        .id();
+    app.update();
+    app.update();
+
+    let hint = *app
+        .world()
+        .resource::<Hints>()
+        .0
+        .last()
+        .expect("a hint is sent");
+    assert_eq!(hint.scale, 2.25);
+    assert_eq!(hint.physical_size, Vec2::new(2160.0, 1215.0));
+    assert_eq!(hint.pixel_count(), 2160 * 1215);
+
+    // Shrinking the window sends a new, smaller hint.
+    app.world_mut()
+        .get_mut::<Window>(window)
+        .unwrap()
+        .resolution
+        .set_physical_resolution(1080, 1080);
+    app.world_mut().write_message(WindowResized {
+        window,
+        width: 540.0,
+        height: 540.0,
+    });
+    app.update();
+    let hint = *app.world().resource::<Hints>().0.last().unwrap();
+    assert_eq!(hint.physical_size, Vec2::new(1080.0, 607.5));
+}
@@ -0,0 +1,286 @@
+use bevy::prelude::*;
+use bevy::window::{PrimaryWindow, WindowResized, WindowResolution};
+use bevy_aspect_ratio_mask::{
+    AspectRatioPlugin, AxisCompensation, FitPolicy, LetterboxChanged, Resolution,
+};
+
+#[derive(Resource, Default)]
+struct LayoutsApplied(usize);
+
+fn count_layouts(mut changed: MessageReader<LetterboxChanged>, mut count: ResMut<LayoutsApplied>) {
+    count.0 += changed.read().count();
+}
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AspectRatioPlugin::default()))
+        .init_resource::<UiScale>()
+        .init_resource::<LayoutsApplied>()
+        .add_message::<WindowResized>()
+        .add_systems(Last, count_layouts);
+    app.world_mut().spawn((
+        Window {
+            resolution: WindowResolution::new(1920, 1080),
+            ..default()
+        },
+        PrimaryWindow,
+    ));
+    app.update();
+    app
+}
+
+#[test]
+fn settings_changed_together_apply_once() {
+    let mut app = app();
+    let before = app.world().resource::<LayoutsApplied>().0;
+
+    app.world_mut().insert_resource(Resolution {
+        width: 640.0,
+        height: 480.0,
+    });
+    app.world_mut().insert_resource(FitPolicy::Cover);
+    app.world_mut().insert_resource(AxisCompensation::default());
+    app.world_mut().write_message(WindowResized {
+        window: Entity::PLACEHOLDER,
+        width: 1920.0,
+        height: 1080.0,
+    });
+    app.update();
+
+    assert_eq!(app.world().resource::<LayoutsApplied>().0, before + 1);
+
+    // Nothing changed since: no further relayout.
+    app.update();
+    assert_eq!(app.world().resource::<LayoutsApplied>().0, before + 1);
+}
+
+#[cfg(feature = "testing")]
+#[test]
+fn synthetic_window_drives_layout_without_a_window() {
+    use bevy_aspect_ratio_mask::{Letterbox, SyntheticWindowSize};
+
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AspectRatioPlugin::default()))
+        .init_resource::<UiScale>()
+        .add_message::<WindowResized>()
+        .insert_resource(SyntheticWindowSize::new(1920.0, 1080.0));
+    app.update();
+
+    let letterbox = *app.world().resource::<Letterbox>();
+    assert_eq!(letterbox.scale, 2.0);
+    assert_eq!(letterbox.bars, Vec2::ZERO);
+
+    // A 4:3 window letterboxes the 16:9 resolution.
+    app.insert_resource(SyntheticWindowSize::new(1440.0, 1080.0));
+    app.update();
+
+    let letterbox = *app.world().resource::<Letterbox>();
+    assert_eq!(letterbox.scale, 1.5);
+    assert_eq!(letterbox.bars, Vec2::new(0.0, 90.0));
+}
+
+#[test]
+fn startup_resolution_applies_on_the_first_frame() {
+    use bevy_aspect_ratio_mask::{AspectRatioMaskSide, AspectRatioSystems, Hud, Letterbox};
+
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AspectRatioPlugin::default()))
+        .init_resource::<UiScale>()
+        .add_message::<WindowResized>()
+        .init_resource::<LayoutsApplied>()
+        .add_systems(Startup, |mut commands: Commands| {
+            commands.insert_resource(Resolution {
+                width: 640.0,
+                height: 360.0,
+            });
+        })
+        // Systems running before the relayout already see the startup layout.
+        .add_systems(
+            Update,
+            (|letterbox: Res<Letterbox>, mut count: ResMut<LayoutsApplied>| {
+                assert_eq!(letterbox.scale, 3.0);
+                count.0 += 1;
+            })
+            .before(AspectRatioSystems::Relayout),
+        );
+    app.world_mut().spawn((
+        Window {
+            resolution: WindowResolution::new(1920, 1080),
+            ..default()
+        },
+        PrimaryWindow,
+    ));
+    app.update();
+
+    assert_eq!(app.world().resource::<LayoutsApplied>().0, 1);
+    assert_eq!(app.world().resource::<UiScale>().0, 3.0);
+    let hud = app.world().resource::<Hud>().0;
+    let node = app.world().get::<Node>(hud).unwrap();
+    assert_eq!(node.width, Val::Px(640.0));
+    assert_eq!(node.height, Val::Px(360.0));
+    let mut bars = app.world_mut().query::<(&AspectRatioMaskSide, &Node)>();
+    for (_, node) in bars.iter(app.world()) {
+        assert!(node.width == Val::Px(0.0) || node.height == Val::Px(0.0));
+    }
+}
+
+#[test]
+fn free_resolution_follows_the_window_until_locked() {
+    use bevy_aspect_ratio_mask::{Letterbox, ResolutionMode};
+
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AspectRatioPlugin {
+            resolution_mode: ResolutionMode::Free,
+            ..default()
+        },
+    ))
+    .init_resource::<UiScale>()
+    .add_message::<WindowResized>();
+    app.world_mut().spawn((
+        Window {
+            resolution: WindowResolution::new(1280, 720),
+            ..default()
+        },
+        PrimaryWindow,
+    ));
+    app.update();
+
+    let resolution = *app.world().resource::<Resolution>();
+    assert_eq!(resolution.size(), Vec2::new(1280.0, 720.0));
+    assert_eq!(app.world().resource::<Letterbox>().scale, 1.0);
+
+    app.insert_resource(Resolution {
+        width: 640.0,
+        height: 320.0,
+    });
+    app.insert_resource(ResolutionMode::Locked);
+    app.update();
+
+    let letterbox = *app.world().resource::<Letterbox>();
+    assert_eq!(letterbox.scale, 2.0);
+    assert_eq!(letterbox.bars, Vec2::new(0.0, 20.0));
+}
+
+#[test]
+fn lazy_bars_only_exist_while_shown() {
+    use bevy_aspect_ratio_mask::{AspectRatioMaskSide, MaskRegions};
+
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AspectRatioPlugin {
+            lazy_bars: true,
+            ..default()
+        },
+    ))
+    .init_resource::<UiScale>()
+    .add_message::<WindowResized>();
+    let window = app
+        .world_mut()
+        .spawn((
+            Window {
+                resolution: WindowResolution::new(1920, 1080),
+                ..default()
+            },
+            PrimaryWindow,
+        ))
+        .id();
+    app.update();
+
+    let mut bars = app.world_mut().query::<&AspectRatioMaskSide>();
+    assert!(app.world().get_resource::<MaskRegions>().is_none());
+    assert_eq!(bars.iter(app.world()).count(), 0);
+
+    let resize = |app: &mut App, width: u32| {
+        let mut entity = app.world_mut().entity_mut(window);
+        entity.get_mut::<Window>().unwrap().resolution = WindowResolution::new(width, 1080);
+        app.world_mut().write_message(WindowResized {
+            window,
+            width: width as f32,
+            height: 1080.0,
+        });
+        app.update();
+    };
+
+    resize(&mut app, 1440);
+    let regions = *app.world().resource::<MaskRegions>();
+    assert_eq!(bars.iter(app.world()).count(), 4);
+    let top = app.world().get::<Node>(regions.top).unwrap();
+    assert_eq!(top.height, Val::Px(90.0));
+
+    resize(&mut app, 1920);
+    assert!(app.world().get_resource::<MaskRegions>().is_none());
+    assert_eq!(bars.iter(app.world()).count(), 0);
+}
+
+#[test]
+fn scale_rounding_applies_to_the_ui_scale() {
+    use bevy_aspect_ratio_mask::{Letterbox, ScaleRounding};
+
+    let mut app = app();
+    // 1920×1080 fits 800×600 at 1.8.
+    app.insert_resource(Resolution {
+        width: 800.0,
+        height: 600.0,
+    });
+    app.update();
+    assert_eq!(app.world().resource::<Letterbox>().scale, 1.8);
+
+    for (rounding, scale) in [
+        (ScaleRounding::FloorQuarter, 1.75),
+        (ScaleRounding::FloorEighth, 1.75),
+        (ScaleRounding::Integer, 1.0),
+    ] {
+        app.insert_resource(rounding);
+        app.update();
+        assert_eq!(app.world().resource::<Letterbox>().scale, scale);
+        assert_eq!(app.world().resource::<UiScale>().0, scale);
+    }
+
+    // 1920×1080 fits 1600×900 at 1.2, nearer to 1.25 than to 1.125: still rounded down.
+    app.insert_resource(Resolution {
+        width: 1600.0,
+        height: 900.0,
+    });
+    app.insert_resource(ScaleRounding::FloorEighth);
+    app.update();
+    assert_eq!(app.world().resource::<Letterbox>().scale, 1.125);
+
+    // 1920×1080 fits 2400×1350 at 0.8, below one integer step: left unrounded.
+    app.insert_resource(Resolution {
+        width: 2400.0,
+        height: 1350.0,
+    });
+    app.insert_resource(ScaleRounding::Integer);
+    app.update();
+    assert_eq!(app.world().resource::<Letterbox>().scale, 0.8);
+}
+
+#[test]
+fn cooperative_mode_publishes_the_scale_without_writing_ui_scale() {
+    use bevy_aspect_ratio_mask::DesiredUiScale;
+
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AspectRatioPlugin {
+            cooperative_ui_scale: true,
+            ..default()
+        },
+    ))
+    .insert_resource(UiScale(1.25))
+    .add_message::<WindowResized>();
+    app.world_mut().spawn((
+        Window {
+            resolution: WindowResolution::new(1920, 1080),
+            ..default()
+        },
+        PrimaryWindow,
+    ));
+    app.update();
+
+    assert_eq!(app.world().resource::<DesiredUiScale>().0, 2.0);
+    assert_eq!(app.world().resource::<UiScale>().0, 1.25);
+}
@@ -0,0 +1,131 @@
+use std::time::Duration;
+
+use bevy::input::mouse::MouseButtonInput;
+use bevy::input::touch::TouchInput;
+use bevy::input::ButtonState;
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use bevy::ui::{ComputedNode, UiGlobalTransform};
+use bevy::window::{PrimaryWindow, WindowEvent, WindowResized, WindowResolution};
+use bevy_aspect_ratio_mask::{AspectRatioPlugin, Hud, InputPlayback, InputRecorder, RecordedInput};
+
+#[derive(Resource, Default)]
+struct Cursor(Vec<Vec2>);
+
+fn collect_cursor(mut moved: MessageReader<CursorMoved>, mut cursor: ResMut<Cursor>) {
+    cursor.0.extend(moved.read().map(|event| event.position));
+}
+
+fn app() -> (App, Entity) {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AspectRatioPlugin::default()))
+        .init_resource::<UiScale>()
+        .init_resource::<Cursor>()
+        .add_message::<WindowResized>()
+        .add_message::<WindowEvent>()
+        .add_message::<CursorMoved>()
+        .add_message::<MouseButtonInput>()
+        .add_message::<TouchInput>()
+        .add_systems(Last, collect_cursor)
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            100,
+        )));
+    let window = app
+        .world_mut()
+        .spawn((
+            Window {
+                resolution: WindowResolution::new(1920, 1080),
+                ..default()
+            },
+            PrimaryWindow,
+        ))
+        .id();
+    app.update();
+    (app, window)
+}
+
+/// Lays the 960×540 HUD out over a 16:9 window of `width` physical pixels.
+fn lay_out_hud(app: &mut App, width: f32) {
+    let hud = app.world().resource::<Hud>().0;
+    let size = Vec2::new(width, width * 9.0 / 16.0);
+    app.world_mut().entity_mut(hud).insert((
+        ComputedNode {
+            size,
+            inverse_scale_factor: 960.0 / width,
+            ..default()
+        },
+        UiGlobalTransform::from_translation(size / 2.0),
+    ));
+}
+
+#[test]
+fn recording_replays_onto_the_same_virtual_point_at_another_size() {
+    let (mut app, window) = app();
+    lay_out_hud(&mut app, 1920.0);
+    app.insert_resource(InputRecorder::default());
+    app.world_mut().write_message(CursorMoved {
+        window,
+        position: Vec2::new(480.0, 270.0),
+        delta: None,
+    });
+    app.world_mut().write_message(MouseButtonInput {
+        button: MouseButton::Left,
+        state: ButtonState::Pressed,
+        window,
+    });
+    app.update();
+    let recording = app
+        .world_mut()
+        .remove_resource::<InputRecorder>()
+        .unwrap()
+        .recording;
+    assert_eq!(
+        recording
+            .events
+            .iter()
+            .map(|(_, input)| *input)
+            .collect::<Vec<_>>(),
+        vec![
+            RecordedInput::CursorMoved(Vec2::new(240.0, 135.0)),
+            RecordedInput::MouseButton(MouseButton::Left, ButtonState::Pressed),
+        ]
+    );
+
+    // Replayed into a window twice as large, the cursor lands on the same HUD point.
+    lay_out_hud(&mut app, 3840.0);
+    app.world_mut().resource_mut::<Cursor>().0.clear();
+    app.insert_resource(InputPlayback::new(recording));
+    app.update();
+    app.update();
+    assert_eq!(
+        app.world().resource::<Cursor>().0,
+        vec![Vec2::new(960.0, 540.0)]
+    );
+    assert!(!app.world().contains_resource::<InputPlayback>());
+}
+
+#[cfg(feature = "layout_asset")]
+#[test]
+fn recording_round_trips_through_serde() {
+    use bevy_aspect_ratio_mask::InputRecording;
+
+    let recording = InputRecording {
+        events: vec![
+            (0.0, RecordedInput::CursorMoved(Vec2::new(240.0, 135.0))),
+            (
+                0.5,
+                RecordedInput::MouseButton(MouseButton::Left, ButtonState::Released),
+            ),
+            (
+                1.0,
+                RecordedInput::Touch {
+                    id: 3,
+                    phase: bevy::input::touch::TouchPhase::Moved,
+                    position: Vec2::new(10.0, 20.0),
+                },
+            ),
+        ],
+    };
+    let text = ron::to_string(&recording).unwrap();
+    assert_eq!(ron::from_str::<InputRecording>(&text).unwrap(), recording);
+}
@@ -0,0 +1,49 @@
+use bevy::prelude::*;
+use bevy::window::{PrimaryWindow, WindowResized, WindowResolution};
+use bevy_aspect_ratio_mask::{AspectRatioPlugin, LayoutSnapshot};
+
+fn snapshot() -> LayoutSnapshot {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AspectRatioPlugin::default()))
+        .init_resource::<UiScale>()
+        .add_message::<WindowResized>();
+    // A 16:10 window twice the width of the 960×540 resolution.
+    app.world_mut().spawn((
+        Window {
+            resolution: WindowResolution::new(1920, 1200),
+            ..default()
+        },
+        PrimaryWindow,
+    ));
+    app.update();
+    LayoutSnapshot::from_world(app.world()).expect("the plugin is set up")
+}
+
+#[test]
+fn snapshot_records_the_current_layout() {
+    let snapshot = snapshot();
+
+    assert_eq!(snapshot.version, LayoutSnapshot::VERSION);
+    assert_eq!(snapshot.resolution, [960.0, 540.0]);
+    assert_eq!(snapshot.fit_policy, "letterbox");
+    assert_eq!(snapshot.scale, 2.0);
+    assert_eq!(snapshot.container_size, [1920.0, 1200.0]);
+    assert_eq!(snapshot.bars, [0.0, 30.0]);
+    assert_eq!(snapshot.virtual_size, [960.0, 540.0]);
+    assert_eq!(snapshot.virtual_rect, [0.0, 60.0, 1920.0, 1140.0]);
+}
+
+#[cfg(feature = "layout_asset")]
+#[test]
+fn snapshot_round_trips_and_reads_older_versions() {
+    let snapshot = snapshot();
+    let text = ron::to_string(&snapshot).unwrap();
+    assert_eq!(ron::from_str::<LayoutSnapshot>(&text).unwrap(), snapshot);
+
+    // Fields missing from an older snapshot take their defaults.
+    let older: LayoutSnapshot = ron::from_str("(version: 0, scale: 2.0)").unwrap();
+    assert_eq!(older.version, 0);
+    assert_eq!(older.scale, 2.0);
+    assert_eq!(older.fit_policy, "");
+    assert_eq!(older.bars, [0.0, 0.0]);
+}
@@ -0,0 +1,47 @@
+use bevy::prelude::*;
+use bevy::window::{PrimaryWindow, WindowResized, WindowResolution};
+use bevy_aspect_ratio_mask::{AspectRatioPlugin, PixelScaledSprite};
+
+#[test]
+fn sprite_texels_cover_whole_window_pixels() {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AspectRatioPlugin::default()))
+        .init_resource::<UiScale>()
+        .add_message::<WindowResized>();
+    // The 960×540 resolution at a scale of 2.5.
+    let window = app
+        .world_mut()
+        .spawn((
+            Window {
+                resolution: WindowResolution::new(2400, 1350),
+                ..default()
+            },
+            PrimaryWindow,
+        ))
+        .id();
+    app.update();
+    let sprite = app
+        .world_mut()
+        .spawn(PixelScaledSprite::new(Vec2::new(16.0, 24.0)))
+        .id();
+    app.update();
+
+    // Each texel covers 2×2 window pixels: 32×48 pixels, or 12.8×19.2 world units.
+    let size = app.world().get::<Sprite>(sprite).unwrap().custom_size;
+    assert_eq!(size, Some(Vec2::new(32.0, 48.0) / 2.5));
+
+    // The size steps when the scale reaches the next whole number.
+    app.world_mut()
+        .get_mut::<Window>(window)
+        .unwrap()
+        .resolution
+        .set_physical_resolution(2880, 1620);
+    app.world_mut().write_message(WindowResized {
+        window,
+        width: 2880.0,
+        height: 1620.0,
+    });
+    app.update();
+    let size = app.world().get::<Sprite>(sprite).unwrap().custom_size;
+    assert_eq!(size, Some(Vec2::new(16.0, 24.0)));
+}
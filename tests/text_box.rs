@@ -0,0 +1,63 @@
+use bevy::prelude::*;
+use bevy::text::{LineHeight, TextPlugin};
+use bevy::window::WindowResized;
+use bevy_aspect_ratio_mask::{AspectRatioPlugin, Hud, TextBox, TextBoxPages};
+
+const TEXT: &str = "one two three four five six seven eight nine ten eleven twelve";
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin::default(),
+        ImagePlugin::default(),
+        TextPlugin,
+        AspectRatioPlugin::default(),
+    ))
+    .init_resource::<UiScale>()
+    .add_message::<WindowResized>();
+    app.update();
+    app
+}
+
+#[test]
+fn text_box_splits_pages_and_clamps_to_the_last() {
+    let mut app = app();
+    let hud = app.world().resource::<Hud>().0;
+    // Two 24 pixel lines per page.
+    let text_box = app
+        .world_mut()
+        .spawn((
+            TextBox::new(TEXT, Vec2::new(120.0, 50.0)),
+            TextFont::from_font_size(20.0),
+            LineHeight::Px(24.0),
+            ChildOf(hud),
+        ))
+        .id();
+    app.update();
+
+    let pages = app.world().get::<TextBoxPages>(text_box).unwrap().clone();
+    assert!(pages.len() > 1);
+    let lines: Vec<&str> = (0..pages.len())
+        .flat_map(|page| pages.get(page).unwrap().lines())
+        .collect();
+    assert!((0..pages.len()).all(|page| pages.get(page).unwrap().lines().count() <= 2));
+    assert_eq!(lines.join(" "), TEXT);
+    assert_eq!(
+        app.world().get::<Text>(text_box).unwrap().0,
+        pages.get(0).unwrap()
+    );
+
+    // Pages past the end show the last one, and turning stops there.
+    let last = pages.len() - 1;
+    app.world_mut().get_mut::<TextBox>(text_box).unwrap().page = last + 5;
+    app.update();
+    assert_eq!(
+        app.world().get::<Text>(text_box).unwrap().0,
+        pages.get(last).unwrap()
+    );
+    let mut turned = TextBox::new(TEXT, Vec2::new(120.0, 50.0));
+    turned.page = last;
+    assert!(!turned.next_page(&pages));
+    assert_eq!(turned.page, last);
+}
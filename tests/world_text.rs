@@ -0,0 +1,67 @@
+#![cfg(feature = "camera")]
+
+use bevy::prelude::*;
+use bevy::text::TextLayoutInfo;
+use bevy::window::{PrimaryWindow, WindowResized, WindowResolution};
+use bevy_aspect_ratio_mask::{AspectRatioPlugin, WorldText};
+
+fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, AspectRatioPlugin::default()))
+        .init_resource::<UiScale>()
+        .add_message::<WindowResized>();
+    // Twice the 960×540 resolution: one world unit is two window pixels.
+    app.world_mut().spawn((
+        Window {
+            resolution: WindowResolution::new(1920, 1080),
+            ..default()
+        },
+        PrimaryWindow,
+    ));
+    app.update();
+    app
+}
+
+#[test]
+fn world_text_is_rasterized_at_the_hud_scale() {
+    let mut app = app();
+    let text = app
+        .world_mut()
+        .spawn(WorldText::new(16.0, Vec2::new(100.0, 50.0)))
+        .id();
+    app.update();
+
+    assert_eq!(app.world().get::<TextFont>(text).unwrap().font_size, 32.0);
+    let transform = app.world().get::<Transform>(text).unwrap();
+    assert_eq!(transform.scale, Vec3::new(0.5, 0.5, 1.0));
+    assert_eq!(transform.translation.truncate(), Vec2::new(100.0, 50.0));
+}
+
+#[test]
+fn world_text_stays_inside_the_visible_world() {
+    let mut app = app();
+    // 200×40 window pixels of text: 100×20 world units.
+    let text = app
+        .world_mut()
+        .spawn((
+            WorldText::new(16.0, Vec2::new(470.0, -300.0)),
+            TextLayoutInfo {
+                size: Vec2::new(200.0, 40.0),
+                scale_factor: 1.0,
+                ..default()
+            },
+        ))
+        .id();
+    app.update();
+
+    let transform = app.world().get::<Transform>(text).unwrap();
+    assert_eq!(transform.translation.truncate(), Vec2::new(430.0, -260.0));
+
+    app.world_mut()
+        .get_mut::<WorldText>(text)
+        .unwrap()
+        .keep_visible = false;
+    app.update();
+    let transform = app.world().get::<Transform>(text).unwrap();
+    assert_eq!(transform.translation.truncate(), Vec2::new(470.0, -300.0));
+}